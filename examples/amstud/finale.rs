@@ -1,3 +1,4 @@
+use crate::FINALE;
 use bevy::prelude::*;
 use bevy_adventure::prelude::*;
 
@@ -28,7 +29,7 @@ fn finale_description(iomgr: Res<IOManager>, mut cmds: Commands) {
 
 pub fn build(mut cmds: Commands) {
     cmds.spawn(Room {
-        name: "Finale",
+        name: FINALE.name(),
         description: None,
     })
     .on_enter_room(finale_description);
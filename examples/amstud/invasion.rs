@@ -1,3 +1,4 @@
+use crate::{INVASION, REMOVE_ONE};
 use bevy::prelude::*;
 use bevy_adventure::prelude::*;
 
@@ -35,12 +36,10 @@ fn invasion(mut iomgr: ResMut<IOManager>) {
 }
 
 // When the player takes their kids with them
+// (Only runs if the player hasn't already taken them)
 fn take_kids(iomgr: Res<IOManager>, mut checkpoints: ResMut<Checkpoints>) {
-    // Check if the player already did this, if not trigger the talk event
-    if checkpoints.0.contains(&"kids") {
-        iomgr.println("You grab your kids.");
-        checkpoints.0.push("kids");
-    }
+    iomgr.println("You grab your kids.");
+    checkpoints.0.push("kids");
 }
 
 // When the player talks to their kids
@@ -124,7 +123,7 @@ fn soldier_encounter(iomgr: Res<IOManager>, checkpoints: ResMut<Checkpoints>, mu
     iomgr.println("\n\nEND CHAPTER 1");
     iomgr.println("Proceed to next chapter? (No will quit game)");
     if iomgr.yes_no_prompt() {
-        cmds.set_room("Remove One");
+        cmds.set_room(REMOVE_ONE);
     } else {
         iomgr.println("Goodbye!");
         cmds.quit_game();
@@ -133,15 +132,19 @@ fn soldier_encounter(iomgr: Res<IOManager>, checkpoints: ResMut<Checkpoints>, mu
 
 pub fn build(mut commands: Commands) {
     let kids = commands
-        .spawn((Name("kids"), Aliases(vec!["children"])))
-        .on_interact(WordType::Take, take_kids)
+        .spawn((
+            Name("kids"),
+            Aliases(vec!["children"]),
+            Refusal("Your kids are already with you."),
+        ))
+        .on_interact_if(WordType::Take, NotFlag("kids"), take_kids)
         .on_interact(WordType::Talk, talk_to_kids)
         .on_interact(WordType::Move, talk_to_kids)
         .id();
 
     commands
         .spawn(Room {
-            name: "Invasion",
+            name: INVASION.name(),
             description: None,
         })
         .on_enter_room(invasion)
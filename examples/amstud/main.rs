@@ -9,6 +9,18 @@ mod remove_five;
 mod remove_one;
 mod remove_two_three;
 
+// Every room in the game
+pub const OPENING: RoomId = RoomId::new("Opening");
+pub const INVASION: RoomId = RoomId::new("Invasion");
+pub const REMOVE_ONE: RoomId = RoomId::new("Remove One");
+pub const REMOVE_TWO: RoomId = RoomId::new("Remove Two");
+pub const REMOVE_THREE: RoomId = RoomId::new("Remove Three");
+pub const REMOVE_FIVE: RoomId = RoomId::new("Remove Five");
+pub const REMOVE_EIGHT: RoomId = RoomId::new("Remove Eight");
+pub const KING_PHILIP: RoomId = RoomId::new("King Philip");
+pub const REMOVE_EIGHTEEN: RoomId = RoomId::new("Remove Eighteen");
+pub const FINALE: RoomId = RoomId::new("Finale");
+
 fn opening_text(mut iomgr: ResMut<IOManager>, mut commands: Commands) {
     // Clear screen, hide cursor
     iomgr.hide_cursor();
@@ -36,12 +48,12 @@ fn opening_text(mut iomgr: ResMut<IOManager>, mut commands: Commands) {
         commands.quit_game();
     }
 
-    commands.set_room("Invasion");
+    commands.set_room(INVASION);
 }
 
 fn build_opening(mut cmds: Commands) {
     cmds.spawn(Room {
-        name: "Opening",
+        name: OPENING.name(),
         description: None,
     })
     .on_enter_room(opening_text)
@@ -51,14 +63,14 @@ fn build_opening(mut cmds: Commands) {
 fn main() {
     // Bevy app
     App::new()
-        .add_plugin(AdventurePlugin)
-        .add_startup_system(build_opening)
-        .add_startup_system(invasion::build)
-        .add_startup_system(remove_one::build)
-        .add_startup_system(remove_two_three::build)
-        .add_startup_system(remove_five::build)
-        .add_startup_system(remove_eight::build)
-        .add_startup_system(remove_eighteen::build)
-        .add_startup_system(finale::build)
+        .add_plugin(AdventurePlugin::new("amstud"))
+        .add_world_builder(build_opening)
+        .add_world_builder(invasion::build)
+        .add_world_builder(remove_one::build)
+        .add_world_builder(remove_two_three::build)
+        .add_world_builder(remove_five::build)
+        .add_world_builder(remove_eight::build)
+        .add_world_builder(remove_eighteen::build)
+        .add_world_builder(finale::build)
         .run();
 }
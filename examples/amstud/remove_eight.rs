@@ -1,3 +1,4 @@
+use crate::{KING_PHILIP, REMOVE_EIGHT, REMOVE_FIVE};
 use bevy::prelude::*;
 use bevy_adventure::prelude::*;
 type Name = bevy_adventure::prelude::Name;
@@ -146,7 +147,7 @@ fn king_philip_description(
     iomgr.println("\n\nEND CHAPTER 5");
     iomgr.println("Proceed to next chapter? (No will quit game)");
     if iomgr.yes_no_prompt() {
-        cmds.set_room("Remove Five");
+        cmds.set_room(REMOVE_FIVE);
     } else {
         iomgr.println("Goodbye!");
         cmds.quit_game();
@@ -160,9 +161,10 @@ fn talk_to_joseph(iomgr: Res<IOManager>) {
         "Joseph also expresses that he wishes he could read the Bible, but has none."
     ));
 }
-fn give_bible_to_joseph(iomgr: Res<IOManager>, inv: Res<Inventory>, query: Query<(Entity, &Name)>) {
-    let (bible_entity, _) = query.iter().find(|(_, name)| name.0 == "bible").unwrap();
-    if inv.0.contains(&bible_entity) {
+fn give_bible_to_joseph(iomgr: Res<IOManager>, command: Res<PlayerCommand>, names: Query<&Name>) {
+    // Joseph's handler runs for "give bible to joseph" (see `give_bible_to_joseph` in tests/objects.rs)
+    let gift = command.direct_object.and_then(|item| names.get(item).ok());
+    if gift.is_some_and(|name| name.0 == "bible") {
         iomgr.println(concat!(
             "Joseph eagerly accepts your Bible, and reads for a bit. ",
             "Joseph seems greatly comforted by the scripture."
@@ -175,7 +177,7 @@ fn river(iomgr: Res<IOManager>, mut cmds: Commands) {
         "The group keeps walking until that night, and then stops to rest. ",
         "The next morning, you cross the river, into King Philip's land."
     ));
-    cmds.set_room("King Philip");
+    cmds.set_room(KING_PHILIP);
 }
 
 pub fn build(mut cmds: Commands) {
@@ -185,13 +187,17 @@ pub fn build(mut cmds: Commands) {
         .id();
 
     let joseph = cmds
-        .spawn((Name("joseph"), Aliases(vec!["son", "child"])))
+        .spawn((
+            Name("joseph"),
+            Aliases(vec!["son", "child"]),
+            Pronouns(vec!["him"]),
+        ))
         .on_interact(WordType::Talk, talk_to_joseph)
         .on_interact(WordType::Give, give_bible_to_joseph)
         .id();
 
     cmds.spawn(Room {
-        name: "Remove Eight",
+        name: REMOVE_EIGHT.name(),
         description: None,
     })
     .add_child(joseph)
@@ -199,7 +205,7 @@ pub fn build(mut cmds: Commands) {
     .on_enter_room(eighth_remove_description);
 
     cmds.spawn(Room {
-        name: "King Philip",
+        name: KING_PHILIP.name(),
         description: None,
     })
     .on_enter_room(king_philip_description);
@@ -1,3 +1,4 @@
+use crate::{REMOVE_EIGHTEEN, REMOVE_FIVE};
 use bevy::prelude::*;
 use bevy_adventure::prelude::*;
 
@@ -35,7 +36,7 @@ fn sleep(iomgr: Res<IOManager>, mut cmds: Commands) {
     iomgr.println("\n\nEND CHAPTER 6");
     iomgr.println("Proceed to finale? (No will quit game)");
     if iomgr.yes_no_prompt() {
-        cmds.set_room("Remove Five");
+        cmds.set_room(REMOVE_FIVE);
     } else {
         iomgr.println("Goodbye!");
         cmds.quit_game();
@@ -106,7 +107,7 @@ pub fn build(mut cmds: Commands) {
         .id();
 
     cmds.spawn(Room {
-        name: "Remove Eighteen",
+        name: REMOVE_EIGHTEEN.name(),
         description: None,
     })
     .on_enter_room(eighteenth_remove_description)
@@ -1,3 +1,4 @@
+use crate::{REMOVE_EIGHT, REMOVE_FIVE};
 use bevy::prelude::*;
 use bevy_adventure::prelude::*;
 
@@ -110,7 +111,7 @@ fn sleep(iomgr: Res<IOManager>, mut cmds: Commands) {
     iomgr.println("\n\nEND CHAPTER 4");
     iomgr.println("Proceed to next chapter? (No will quit game)");
     if iomgr.yes_no_prompt() {
-        cmds.set_room("Remove Eight");
+        cmds.set_room(REMOVE_EIGHT);
     } else {
         iomgr.println("Goodbye!");
         cmds.quit_game();
@@ -134,7 +135,7 @@ pub fn build(mut cmds: Commands) {
         .id();
 
     cmds.spawn(Room {
-        name: "Remove Five",
+        name: REMOVE_FIVE.name(),
         description: None,
     })
     .on_enter_room(fifth_remove_description)
@@ -1,3 +1,4 @@
+use crate::{REMOVE_ONE, REMOVE_TWO};
 use bevy::prelude::*;
 use bevy_adventure::prelude::*;
 
@@ -128,7 +129,7 @@ fn sleep(mut cmds: Commands, iomgr: Res<IOManager>, checkpoints: Res<Checkpoints
     iomgr.println("\n\nEND CHAPTER 2");
     iomgr.println("Proceed to next chapter? (No will quit game)");
     if iomgr.yes_no_prompt() {
-        cmds.set_room("Remove Two");
+        cmds.set_room(REMOVE_TWO);
     } else {
         iomgr.println("Goodbye!");
         cmds.quit_game();
@@ -158,7 +159,7 @@ pub fn build(mut cmds: Commands) {
         .id();
 
     cmds.spawn(Room {
-        name: "Remove One",
+        name: REMOVE_ONE.name(),
         description: None,
     })
     .on_enter_room(first_remove_description)
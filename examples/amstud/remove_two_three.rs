@@ -1,3 +1,4 @@
+use crate::{REMOVE_FIVE, REMOVE_THREE, REMOVE_TWO};
 use bevy::prelude::*;
 use bevy_adventure::prelude::*;
 type Name = bevy_adventure::prelude::Name;
@@ -90,7 +91,7 @@ fn second_remove_description(
         );
     }
 
-    cmds.set_room("Remove Three");
+    cmds.set_room(REMOVE_THREE);
 }
 
 fn third_remove_description(mut iomgr: ResMut<IOManager>, checkpoints: Res<Checkpoints>) {
@@ -191,7 +192,7 @@ fn sleep(iomgr: Res<IOManager>, mut cmds: Commands, checkpoints: Res<Checkpoints
     iomgr.println("\n\nEND CHAPTER 3");
     iomgr.println("Proceed to next chapter? (No will quit game)");
     if iomgr.yes_no_prompt() {
-        cmds.set_room("Remove Five");
+        cmds.set_room(REMOVE_FIVE);
     } else {
         iomgr.println("Goodbye!");
         cmds.quit_game();
@@ -269,13 +270,13 @@ pub fn build(mut cmds: Commands) {
         .on_interact(WordType::Any, use_oak_leaves);
 
     cmds.spawn(Room {
-        name: "Remove Two",
+        name: REMOVE_TWO.name(),
         description: None,
     })
     .on_enter_room(second_remove_description);
 
     cmds.spawn(Room {
-        name: "Remove Three",
+        name: REMOVE_THREE.name(),
         description: None,
     })
     .on_enter_room(third_remove_description)
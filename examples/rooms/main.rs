@@ -1,10 +1,14 @@
 use bevy::prelude::*;
 use bevy_adventure::prelude::*;
 
+// Room IDs - the rooms, exits & set_room calls all use these, so a misspelled name won't compile
+const ROOM_ONE: RoomId = RoomId::new("Room 1");
+const ROOM_TWO: RoomId = RoomId::new("Room 2");
+
 fn door_callback(mut commands: Commands, current_room: Query<&Room, With<ActiveRoom>>) {
-    match current_room.single().name {
-        "Room 1" => commands.set_room("Room 2"),
-        "Room 2" => commands.set_room("Room 1"),
+    match current_room.single().id() {
+        ROOM_ONE => commands.set_room(ROOM_TWO),
+        ROOM_TWO => commands.set_room(ROOM_ONE),
         _ => panic!("Door opened from room that isn't Room 1 or Room 2"),
     }
 }
@@ -21,19 +25,22 @@ fn build_level_one(mut commands: Commands) {
     // Room 1
     let initial_room = commands
         .spawn(Room {
-            name: "Room 1",
+            name: ROOM_ONE.name(),
             description: Some("You're in room 1"),
         })
         .insert(ActiveRoom)
+        // Room 2 is to the east, so "go east" or "e" also works
+        .insert(Exits::new().with(Direction::East, ROOM_TWO))
         .add_child(doorway)
         .id();
 
     // Room 2
     let room_two = commands
         .spawn(Room {
-            name: "Room 2",
+            name: ROOM_TWO.name(),
             description: Some("You're in room 2"),
         })
+        .insert(Exits::new().with(Direction::West, ROOM_ONE))
         .add_child(doorway_two)
         .id();
 
@@ -46,7 +53,7 @@ fn build_level_one(mut commands: Commands) {
 
 fn main() {
     App::new()
-        .add_plugin(AdventurePlugin)
-        .add_startup_system(build_level_one)
+        .add_plugin(AdventurePlugin::new("rooms"))
+        .add_world_builder(build_level_one)
         .run();
 }
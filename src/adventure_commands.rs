@@ -1,70 +1,216 @@
 use crate::components::{ActiveRoom, OnDeath, OnEnterRoom, OnInteract, Room};
-use crate::input_output_manager::WordType;
+use crate::error::{self, AdventureError};
+use crate::input_output_manager::{IOManager, WordType};
+use crate::player;
+use crate::rooms::{RoomChanged, RoomId, RoomIndex};
+use crate::save;
+use crate::systems;
 use bevy::app::AppExit;
 use bevy::ecs::system::EntityCommands;
 use bevy::prelude::{Commands, Entity, With, World};
+use std::path::PathBuf;
 
 /// Add methods to Bevy's [Commands] struct
 pub trait AdventureCommands {
     /// Change the active room in the game
     ///
-    /// Just pass the name of the room to make active.
-    /// This should match the string in that room's `name` field.
+    /// Pass the [RoomId] of the room to make active - its name should match the string in
+    /// that room's `name` field, so declare it once as a constant and use it for both.
     ///
     /// Check the
     /// [rooms example](https://github.com/Bright-Shard/bevy_adventure/tree/master/examples/rooms)
     /// for an example on how to use this.
-    fn set_room(&mut self, room: &str);
+    ///
+    /// This panics if the room doesn't exist; use [AdventureCommands::try_set_room] to handle that instead.
+    fn set_room(&mut self, room: impl Into<RoomId>);
+    /// Change the active room in the game, without panicking if the room doesn't exist.
+    ///
+    /// If the room is missing, the player stays in their current room and an
+    /// [AdventureError::RoomNotFound] is reported - it's printed to stderr, sent as an event, and passed to the
+    /// handler set with [AdventureApp::set_error_handler](crate::plugin::AdventureApp::set_error_handler).
+    fn try_set_room(&mut self, room: impl Into<RoomId>);
+    /// Move an entity into the player's [Inventory](crate::player::Inventory), removing it from its room.
+    ///
+    /// This is what taking a [Portable](crate::components::Portable) entity does, so custom `Take`
+    /// handlers can call it once they've decided the player is allowed to take something.
+    fn take_item(&mut self, entity: Entity) -> &mut Self;
+    /// Move an entity from the player's [Inventory](crate::player::Inventory) into the active room.
+    fn drop_item(&mut self, entity: Entity) -> &mut Self;
     /// Send the [AppExit] event, and quit the game.
     fn quit_game(&mut self) -> &mut Self;
+    /// Restart the game from the beginning, without quitting.
+    ///
+    /// This despawns all of the game's rooms & entities, clears [Flags](crate::flags::Flags)
+    /// and [Inventory](crate::player::Inventory), then rebuilds the world by re-running the systems
+    /// added with [AdventureApp::add_world_builder](crate::plugin::AdventureApp::add_world_builder).
+    /// The starting room's OnEnterRoom event then fires again.
+    ///
+    /// Rooms spawned by ordinary startup systems can't be rebuilt, so if the game has no
+    /// world builders, the player is told it can't be restarted instead.
+    fn restart_game(&mut self) -> &mut Self;
+    /// Save the game's state to a file. The save is labelled with the file's name.
+    ///
+    /// If the game can't be saved, the error is printed to the player.
+    /// See the [save](crate::save) module for what gets saved.
+    fn save_game(&mut self, path: impl Into<PathBuf>) -> &mut Self;
+    /// Load the game's state from a file made with [AdventureCommands::save_game].
+    ///
+    /// If the save can't be loaded, the error is printed to the player and the game is left as-is.
+    fn load_game(&mut self, path: impl Into<PathBuf>) -> &mut Self;
+    /// Save the game's state to a named slot in the [SaveSettings](crate::save::SaveSettings)
+    /// directory, with a player-visible label. Overwrites the slot if it already exists.
+    fn save_slot(&mut self, slot: &str, label: &str) -> &mut Self;
+    /// Load the game's state from a named slot (see [AdventureCommands::save_slot])
+    fn load_slot(&mut self, slot: &str) -> &mut Self;
+    /// Let the player choose a save slot to save in. This is what the `save` command does.
+    fn save_prompt(&mut self) -> &mut Self;
+    /// Let the player choose a save slot to load. This is what the `load` command does.
+    fn load_prompt(&mut self) -> &mut Self;
 }
 impl AdventureCommands for Commands<'_, '_> {
-    fn set_room(&mut self, new_room: &str) {
-        // Get the room name
-        let room_name = new_room.to_owned();
+    fn set_room(&mut self, new_room: impl Into<RoomId>) {
+        let room = new_room.into();
 
         // Add a command to the queue, so we get exclusive world access
+        self.add(move |world: &mut World| set_room(world, room));
+    }
+
+    fn try_set_room(&mut self, new_room: impl Into<RoomId>) {
+        let room = new_room.into();
+
         self.add(move |world: &mut World| {
-            // Remove ActiveRoom for the current room
-            let (entity, room, _) = world
-                .query::<(Entity, &Room, With<ActiveRoom>)>()
-                .single_mut(world);
-            // If the active room is also the room we're making active, just return
-            //  (Otherwise the OnRoom event would fire again)
-            if room.name == room_name {
-                return;
-            }
-            // Remove ActiveRoom
-            world.get_entity_mut(entity).unwrap().remove::<ActiveRoom>();
-
-            // Add ActiveRoom for the newly active room
-            let mut query = world.query::<(Entity, &Room)>();
-            let mut target = None;
-            for (entity, room) in query.iter_mut(world) {
-                if room.name == room_name {
-                    target = Some(entity);
-                }
+            if let Err(err) = try_set_room(world, room) {
+                error::report_error(world, err);
             }
-            match target {
-                Some(room) => world.entity_mut(room).insert(ActiveRoom {}),
-                None => panic!(
-                    "Attempted to set the active room to `{}`, which does not exist",
-                    room_name
-                ),
-            };
         });
     }
 
+    fn take_item(&mut self, entity: Entity) -> &mut Self {
+        self.add(move |world: &mut World| player::take_item(world, entity));
+        self
+    }
+
+    fn drop_item(&mut self, entity: Entity) -> &mut Self {
+        self.add(move |world: &mut World| player::drop_item(world, entity));
+        self
+    }
+
     fn quit_game(&mut self) -> &mut Self {
         self.add(|world: &mut World| {
             world.send_event(AppExit);
         });
         self
     }
+
+    fn restart_game(&mut self) -> &mut Self {
+        self.add(systems::restart_game);
+        self
+    }
+
+    fn save_game(&mut self, path: impl Into<PathBuf>) -> &mut Self {
+        let path = path.into();
+        self.add(move |world: &mut World| {
+            let label = path
+                .file_stem()
+                .map_or_else(String::new, |stem| stem.to_string_lossy().into_owned());
+            if let Err(err) = save::save_game(world, &path, label) {
+                world
+                    .resource::<IOManager>()
+                    .println(format!("Couldn't save the game: {}", err));
+            }
+        });
+        self
+    }
+
+    fn load_game(&mut self, path: impl Into<PathBuf>) -> &mut Self {
+        let path = path.into();
+        self.add(move |world: &mut World| {
+            if let Err(err) = save::load_game(world, path) {
+                world
+                    .resource::<IOManager>()
+                    .println(format!("Couldn't load the game: {}", err));
+            }
+        });
+        self
+    }
+
+    fn save_slot(&mut self, slot: &str, label: &str) -> &mut Self {
+        let slot = slot.to_owned();
+        let label = label.to_owned();
+        self.add(move |world: &mut World| {
+            if let Err(err) = save::save_slot(world, &slot, label) {
+                world
+                    .resource::<IOManager>()
+                    .println(format!("Couldn't save the game: {}", err));
+            }
+        });
+        self
+    }
+
+    fn load_slot(&mut self, slot: &str) -> &mut Self {
+        let slot = slot.to_owned();
+        self.add(move |world: &mut World| {
+            if let Err(err) = save::load_slot(world, &slot) {
+                world
+                    .resource::<IOManager>()
+                    .println(format!("Couldn't load the game: {}", err));
+            }
+        });
+        self
+    }
+
+    fn save_prompt(&mut self) -> &mut Self {
+        self.add(save::save_prompt);
+        self
+    }
+
+    fn load_prompt(&mut self) -> &mut Self {
+        self.add(save::load_prompt);
+        self
+    }
+}
+
+/// Change the active room, with exclusive world access. See [AdventureCommands::set_room].
+pub(crate) fn set_room(world: &mut World, new_room: RoomId) {
+    if let Err(AdventureError::RoomNotFound(room)) = try_set_room(world, new_room) {
+        panic!(
+            "Attempted to set the active room to `{}`, which does not exist",
+            room
+        );
+    }
+}
+
+/// Change the active room, with exclusive world access. If the room doesn't exist,
+/// the active room is left as-is. See [AdventureCommands::try_set_room].
+pub(crate) fn try_set_room(world: &mut World, new_room: RoomId) -> Result<(), AdventureError> {
+    // Remove ActiveRoom for the current room
+    let (entity, room, _) = world
+        .query::<(Entity, &Room, With<ActiveRoom>)>()
+        .single_mut(world);
+    let old_room = room.id();
+    // If the active room is also the room we're making active, just return
+    //  (Otherwise the OnRoom event would fire again)
+    if old_room == new_room {
+        return Ok(());
+    }
+
+    // Look up the newly active room
+    let target = RoomIndex::find(world, new_room).ok_or(AdventureError::RoomNotFound(new_room))?;
+
+    // Move ActiveRoom to the new room
+    world.get_entity_mut(entity).unwrap().remove::<ActiveRoom>();
+    world.entity_mut(target).insert(ActiveRoom {});
+    world.send_event(RoomChanged {
+        from: old_room,
+        to: new_room,
+    });
+    Ok(())
 }
 
 /// Modify Bevy's EntityCommands struct
-use crate::events::IntoEventHandler;
+use crate::conditions::{Condition, HandlerList, IntoCondition};
+use crate::events::{EventHandler, IntoEventHandler};
+use std::sync::{Arc, Mutex};
 
 /// Adds methods to Bevy's [EntityCommands] struct
 pub trait AdventureEntityCommands {
@@ -72,30 +218,66 @@ pub trait AdventureEntityCommands {
     ///
     /// Example:
     /// ```
+    /// # use bevy::prelude::*;
+    /// # use bevy_text_adventure::prelude::*;
+    /// # type Name = bevy_text_adventure::prelude::Name;
+    /// # fn build(mut commands: Commands) {
     /// // Makes a new entity named 'door' that will change rooms when interacted with
     /// let doorway = commands
     ///     // Make a new entity & give it a `Name` component
-    ///     .spawn(Name("door".to_owned()))
+    ///     .spawn(Name("door"))
     ///     // Bind the OnInteract event to a closure that changes the active room
     ///     // The first generic is the event type, and the second is Params
     ///     // Don't worry about the second generic too much, it's just Bevy magic.
     ///     // Just put a `_` to make Rust autodetect it.
-    ///     .bind_event(WordType::Any, |mut commands: Commands|{
+    ///     .on_interact(WordType::Any, |mut commands: Commands|{
     ///         // Change the active room to the second room
-    ///         commands.set_room("Room 2");
+    ///         commands.set_room(RoomId::new("Room 2"));
     ///     })
     ///     // Return the Entity to store in the doorway variable
     ///     .id();
+    /// # }
     /// ```
+    ///
+    /// Handlers can also take an `In<`[InteractionContext](crate::parser::InteractionContext)`>`
+    /// as their first parameter, to see what the player typed and which entities were involved.
     fn on_interact<Params>(
         &mut self,
         word_type: WordType,
         handler: impl IntoEventHandler<Params>,
     ) -> &mut Self;
+    /// Bind an interaction event that only runs when `condition` passes. The condition can be
+    /// a system that returns a [bool], or a [Flag](crate::conditions::Flag) or
+    /// [NotFlag](crate::conditions::NotFlag).
+    ///
+    /// An entity can have several conditional handlers for the same [WordType]. They're checked in
+    /// the order they were added, then the handler from [AdventureEntityCommands::on_interact] runs
+    /// if none of them passed. If nothing for that word type can run, the entity's built-in
+    /// behavior (like taking [Portable](crate::components::Portable) items) or its [WordType::Any]
+    /// handler is used, and then its [Refusal](crate::components::Refusal) message, if it has one.
+    ///
+    /// ```ignore
+    /// commands
+    ///     .spawn((Name("kids"), Refusal("The kids are already with you.")))
+    ///     .on_interact_if(WordType::Take, NotFlag("kids"), take_kids);
+    /// ```
+    fn on_interact_if<Params, ConditionParams>(
+        &mut self,
+        word_type: WordType,
+        condition: impl IntoCondition<ConditionParams>,
+        handler: impl IntoEventHandler<Params>,
+    ) -> &mut Self;
     /// The same as above, but instead binds an on death event to an entity.
     fn on_death<Params>(&mut self, handler: impl IntoEventHandler<Params>) -> &mut Self;
     /// The same as above, but instead binds an on enter event to an entity.
     fn on_enter_room<Params>(&mut self, handler: impl IntoEventHandler<Params>) -> &mut Self;
+    /// Bind an on enter event that only runs when `condition` passes. This works like
+    /// [AdventureEntityCommands::on_interact_if]: the first handler whose condition passes runs.
+    fn on_enter_room_if<Params, ConditionParams>(
+        &mut self,
+        condition: impl IntoCondition<ConditionParams>,
+        handler: impl IntoEventHandler<Params>,
+    ) -> &mut Self;
 }
 
 impl AdventureEntityCommands for EntityCommands<'_, '_, '_> {
@@ -104,19 +286,21 @@ impl AdventureEntityCommands for EntityCommands<'_, '_, '_> {
         word_type: WordType,
         handler: impl IntoEventHandler<Params>,
     ) -> &mut Self {
-        let entity = self.id();
-        let commands = self.commands();
-        commands.add(move |world: &mut World| {
-            let mut entity = world.entity_mut(entity);
-            if let Some(mut on_interact) = entity.get_mut::<OnInteract>() {
-                on_interact.0.insert(word_type, handler.into_event());
-            } else {
-                let mut hashmap = bevy::utils::HashMap::new();
-                hashmap.insert(word_type, handler.into_event());
-                entity.insert(OnInteract(hashmap));
-            }
-        });
-        self
+        add_interaction(self, word_type, None, handler.into_event())
+    }
+
+    fn on_interact_if<Params, ConditionParams>(
+        &mut self,
+        word_type: WordType,
+        condition: impl IntoCondition<ConditionParams>,
+        handler: impl IntoEventHandler<Params>,
+    ) -> &mut Self {
+        add_interaction(
+            self,
+            word_type,
+            Some(condition.into_condition()),
+            handler.into_event(),
+        )
     }
 
     fn on_death<Params>(&mut self, handler: impl IntoEventHandler<Params>) -> &mut Self {
@@ -124,6 +308,61 @@ impl AdventureEntityCommands for EntityCommands<'_, '_, '_> {
     }
 
     fn on_enter_room<Params>(&mut self, handler: impl IntoEventHandler<Params>) -> &mut Self {
-        self.insert(OnEnterRoom(handler.into_event()))
+        add_enter_room(self, None, handler.into_event())
+    }
+
+    fn on_enter_room_if<Params, ConditionParams>(
+        &mut self,
+        condition: impl IntoCondition<ConditionParams>,
+        handler: impl IntoEventHandler<Params>,
+    ) -> &mut Self {
+        add_enter_room(self, Some(condition.into_condition()), handler.into_event())
     }
 }
+
+// Add a handler to an entity's OnInteract, creating it if needed
+fn add_interaction<'a, 'w, 's, 'b>(
+    commands: &'b mut EntityCommands<'w, 's, 'a>,
+    word_type: WordType,
+    condition: Option<Arc<Mutex<dyn Condition>>>,
+    handler: Arc<Mutex<dyn EventHandler>>,
+) -> &'b mut EntityCommands<'w, 's, 'a> {
+    let entity = commands.id();
+    commands.commands().add(move |world: &mut World| {
+        let mut entity = world.entity_mut(entity);
+        if let Some(mut on_interact) = entity.get_mut::<OnInteract>() {
+            on_interact
+                .0
+                .entry(word_type)
+                .or_default()
+                .add(condition, handler);
+        } else {
+            let mut handlers = HandlerList::default();
+            handlers.add(condition, handler);
+            let mut hashmap = bevy::utils::HashMap::new();
+            hashmap.insert(word_type, handlers);
+            entity.insert(OnInteract(hashmap));
+        }
+    });
+    commands
+}
+
+// Add a handler to an entity's OnEnterRoom, creating it if needed
+fn add_enter_room<'a, 'w, 's, 'b>(
+    commands: &'b mut EntityCommands<'w, 's, 'a>,
+    condition: Option<Arc<Mutex<dyn Condition>>>,
+    handler: Arc<Mutex<dyn EventHandler>>,
+) -> &'b mut EntityCommands<'w, 's, 'a> {
+    let entity = commands.id();
+    commands.commands().add(move |world: &mut World| {
+        let mut entity = world.entity_mut(entity);
+        if let Some(mut on_enter_room) = entity.get_mut::<OnEnterRoom>() {
+            on_enter_room.0.add(condition, handler);
+        } else {
+            let mut handlers = HandlerList::default();
+            handlers.add(condition, handler);
+            entity.insert(OnEnterRoom(handlers));
+        }
+    });
+    commands
+}
@@ -22,6 +22,9 @@ impl Health {
 }
 
 /// For naming entities
+///
+/// Names can be several words long (like "front door"), and are matched ignoring case.
+/// If more than one name appears in the player's input, the longest one wins.
 #[derive(Component)]
 pub struct Name(pub &'static str);
 
@@ -29,13 +32,39 @@ pub struct Name(pub &'static str);
 ///
 /// The player can then interact with the entity
 /// by using its alias, in addition to its name.
+/// Aliases are matched the same way as [Name]s.
 #[derive(Component)]
 pub struct Aliases(pub Vec<&'static str>);
 
-/// For giving entities descriptions
+/// The pronouns the player can use for an entity, after mentioning it - for example,
+/// `Pronouns(vec!["him"])` lets the player type "talk to joseph", then "follow him".
+///
+/// Entities without this component can be called "it". When the player mentioned more than one
+/// entity, they can be called "them" together, and the command is done to each of them in turn.
+#[derive(Component)]
+pub struct Pronouns(pub Vec<&'static str>);
+
+/// Marks an entity the player can pick up.
+///
+/// Taking a portable entity moves it from its room into the player's [Inventory](crate::player::Inventory),
+/// and dropping it puts it in the active room. Items in the inventory can be used from any room.
+/// An [OnInteract] handler for [WordType::Take](crate::input_output_manager::WordType::Take)
+/// or [WordType::Drop](crate::input_output_manager::WordType::Drop) replaces this behavior.
+#[derive(Component)]
+pub struct Portable;
+
+/// For giving entities descriptions.
+///
+/// Looking at an entity prints its description, unless it has an [OnInteract] handler for
+/// [WordType::Look](crate::input_output_manager::WordType::Look).
 #[derive(Component)]
 pub struct Description(pub String);
 
+/// Keeps an entity out of the "You see: ..." list printed when the player looks around a room.
+/// The player can still interact with it, if they know its name.
+#[derive(Component)]
+pub struct Hidden;
+
 // ========== LEVEL COMPONENTS ==========
 
 /// A room in the game
@@ -43,9 +72,15 @@ pub struct Description(pub String);
 pub struct Room {
     /// Name of the room
     pub name: &'static str,
-    /// Description of the room (printed w/ autoprompt)
+    /// Description of the room (printed w/ autoprompt, and when the player looks around)
     pub description: Option<&'static str>,
 }
+impl Room {
+    /// The [RoomId] that points to this room
+    pub fn id(&self) -> RoomId {
+        RoomId::new(self.name)
+    }
+}
 
 /// A marker component for the currently active room
 #[derive(Component)]
@@ -55,7 +90,144 @@ pub struct ActiveRoom;
 #[derive(Component)]
 pub struct Level;
 
+// ========== NAVIGATION ==========
+use crate::conditions::{Condition, IntoCondition};
+use crate::rooms::RoomId;
+use bevy::prelude::World;
+
+/// A direction the player can travel in, through one of a room's [Exits]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum Direction {
+    North,
+    South,
+    East,
+    West,
+    Up,
+    Down,
+    In,
+    Out,
+    /// A named exit, like "house" or "front door". The player takes it with a movement
+    /// verb, like "enter house" or "go to the front door".
+    Custom(&'static str),
+}
+impl Direction {
+    /// Parse a compass direction, including abbreviations like "n" for north.
+    /// Custom directions are matched by the room's [Exits] instead.
+    pub fn parse(word: &str) -> Option<Self> {
+        match word.to_lowercase().as_str() {
+            "north" | "n" => Some(Self::North),
+            "south" | "s" => Some(Self::South),
+            "east" | "e" => Some(Self::East),
+            "west" | "w" => Some(Self::West),
+            "up" | "u" => Some(Self::Up),
+            "down" | "d" => Some(Self::Down),
+            "in" | "inside" => Some(Self::In),
+            "out" | "outside" => Some(Self::Out),
+            _ => None,
+        }
+    }
+    /// The player-visible name of the direction
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::North => "north",
+            Self::South => "south",
+            Self::East => "east",
+            Self::West => "west",
+            Self::Up => "up",
+            Self::Down => "down",
+            Self::In => "in",
+            Self::Out => "out",
+            Self::Custom(label) => label,
+        }
+    }
+}
+
+/// One of a room's [Exits]
+#[derive(Clone)]
+pub struct Exit {
+    /// The room this exit leads to
+    pub target: RoomId,
+    /// If set, the exit can only be used when this passes
+    pub condition: Option<Arc<Mutex<dyn Condition>>>,
+    /// Printed when the condition stops the player from using the exit
+    pub blocked_message: Option<&'static str>,
+}
+impl Exit {
+    /// An exit to the room `target`
+    pub fn to(target: impl Into<RoomId>) -> Self {
+        Self {
+            target: target.into(),
+            condition: None,
+            blocked_message: None,
+        }
+    }
+    /// Only let the player use this exit when `condition` passes: a [Flag](crate::conditions::Flag),
+    /// a [NotFlag](crate::conditions::NotFlag), or a system that returns a [bool]
+    pub fn only_if<Params>(mut self, condition: impl IntoCondition<Params>) -> Self {
+        self.condition = Some(condition.into_condition());
+        self
+    }
+    /// Set the message printed when the exit's condition stops the player
+    pub fn blocked_message(mut self, message: &'static str) -> Self {
+        self.blocked_message = Some(message);
+        self
+    }
+    /// Check the exit's condition
+    pub fn is_open(&self, world: &mut World) -> bool {
+        match &self.condition {
+            Some(condition) => condition.lock().unwrap().check(world),
+            None => true,
+        }
+    }
+}
+impl From<RoomId> for Exit {
+    fn from(target: RoomId) -> Self {
+        Self::to(target)
+    }
+}
+
+/// The ways out of a room.
+///
+/// Give this to a [Room], and the player can travel with commands like "go north", "n",
+/// or "enter house", without any `on_interact` handlers.
+///
+/// Example:
+/// ```ignore
+/// commands.spawn((
+///     Room { name: YARD.name(), description: None },
+///     Exits::new()
+///         .with(Direction::North, FIELD)
+///         .with(
+///             Direction::Custom("house"),
+///             Exit::to(HOUSE)
+///                 .only_if(Flag("key"))
+///                 .blocked_message("The door is locked."),
+///         ),
+/// ));
+/// ```
+#[derive(Component, Default)]
+pub struct Exits(pub bevy::utils::HashMap<Direction, Exit>);
+impl Exits {
+    /// A room with no exits
+    pub fn new() -> Self {
+        Self::default()
+    }
+    /// Add an exit in `direction`
+    pub fn with(mut self, direction: Direction, exit: impl Into<Exit>) -> Self {
+        self.0.insert(direction, exit.into());
+        self
+    }
+    /// Find the exit with a custom label (ignoring case)
+    pub fn custom(&self, label: &str) -> Option<(&Direction, &Exit)> {
+        self.0.iter().find(|(direction, _)| match direction {
+            Direction::Custom(custom) => custom.eq_ignore_ascii_case(label),
+            _ => false,
+        })
+    }
+}
+
 // ========== EVENTS ==========
+use crate::conditions::HandlerList;
 use crate::{events::EventHandler, input_output_manager::WordType};
 use std::sync::{Arc, Mutex};
 
@@ -68,10 +240,78 @@ pub struct OnDeath(pub(crate) Handler);
 
 /// When an entity is interacted with
 ///
-/// This event takes one generic - the type of word this interaction handles.
+/// This event stores handlers for each type of word, some of which may only run under
+/// certain conditions (see [AdventureEntityCommands::on_interact_if](crate::adventure_commands::AdventureEntityCommands::on_interact_if)).
 #[derive(Component)]
-pub struct OnInteract(pub(crate) bevy::utils::HashMap<WordType, Handler>);
+pub struct OnInteract(pub(crate) bevy::utils::HashMap<WordType, HandlerList>);
 
 /// When the player enters a room
 #[derive(Component)]
-pub struct OnEnterRoom(pub(crate) Handler);
+pub struct OnEnterRoom(pub(crate) HandlerList);
+
+/// Printed when the player interacts with an entity, but the conditions on all of its
+/// handlers for that action failed.
+///
+/// Without this, the game acts as if the entity had no handler for the action.
+#[derive(Component)]
+pub struct Refusal(pub &'static str);
+
+// ========== DESCRIPTIONS ==========
+use crate::description::{DescriptionSource, IntoDescription};
+
+/// A description that's written when it's printed, so it can change with the game's state.
+/// This replaces an entity's [Description], or a [Room]'s `description`.
+///
+/// It can come from a system that returns a [String]:
+/// ```ignore
+/// DynamicDescription::new(|flags: Res<Flags>| -> String {
+///     if flags.is_set("kids") {
+///         "Your children huddle by the fire.".to_owned()
+///     } else {
+///         "The fire crackles. You're alone.".to_owned()
+///     }
+/// })
+/// ```
+///
+/// Or be chosen from variants, keyed on [Flags](crate::flags::Flags). The first
+/// variant whose flag is set is used:
+/// ```ignore
+/// DynamicDescription::variants("The fire crackles. You're alone.")
+///     .when("sarahShot", "Sarah lies by the fire, badly hurt.")
+///     .when("kids", "Your children huddle by the fire.")
+/// ```
+#[derive(Component, Clone)]
+pub enum DynamicDescription {
+    /// A system that writes the description
+    System(Arc<Mutex<dyn DescriptionSource>>),
+    /// Text chosen by which flags are set
+    Variants {
+        /// Each flag, and the text to use if it's set
+        variants: Vec<(&'static str, String)>,
+        /// The text to use if none of the flags are set
+        default: String,
+    },
+}
+impl DynamicDescription {
+    /// A description written by a system. The system can have any parameters, and returns a [String].
+    pub fn new<Params>(system: impl IntoDescription<Params>) -> Self {
+        Self::System(system.into_description())
+    }
+    /// A description chosen from variants, which uses `default` until a variant is added & reached
+    pub fn variants(default: impl Into<String>) -> Self {
+        Self::Variants {
+            variants: Vec::new(),
+            default: default.into(),
+        }
+    }
+    /// Add a variant, used once `flag` is set (see [Flags::is_set](crate::flags::Flags::is_set)).
+    /// Variants added earlier win if several flags are set.
+    ///
+    /// This does nothing to descriptions made with [DynamicDescription::new].
+    pub fn when(mut self, flag: &'static str, text: impl Into<String>) -> Self {
+        if let Self::Variants { variants, .. } = &mut self {
+            variants.push((flag, text.into()));
+        }
+        self
+    }
+}
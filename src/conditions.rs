@@ -0,0 +1,130 @@
+use crate::events::EventHandler;
+use crate::flags::Flags;
+use bevy::ecs::system::{FunctionSystem, System, SystemParam, SystemParamFunction};
+use bevy::prelude::{IntoSystem, World};
+use std::sync::{Arc, Mutex};
+
+// ========== CONDITION TRAITS ==========
+
+/// A generic trait to store checks that decide if a handler runs.
+pub trait Condition: 'static + Sync + Send {
+    // Run the check
+    fn check(&mut self, world: &mut World) -> bool;
+    // Set up the Condition (FunctionSystems must be initialized before use)
+    fn init(&mut self, world: &mut World);
+}
+
+/// A generic trait to convert types into Conditions.
+pub trait IntoCondition<Params>: Sync + Send + 'static {
+    // Convert the struct to a Condition
+    fn into_condition(self) -> Arc<Mutex<dyn Condition>>
+    where
+        Self: Sized;
+}
+
+/// Implement [Condition] for [FunctionSystem]s that return a [bool].
+impl<Fn, Params> Condition for FunctionSystem<(), bool, Params, (), Fn>
+where
+    Params: SystemParam + 'static,
+    Fn: SystemParamFunction<(), bool, Params, ()>,
+{
+    fn check(&mut self, world: &mut World) -> bool {
+        let passed = self.run((), world);
+        self.apply_buffers(world);
+        passed
+    }
+    fn init(&mut self, world: &mut World) {
+        self.initialize(world);
+    }
+}
+
+/// Implement [IntoCondition] for systems that return a [bool].
+impl<Params, F> IntoCondition<Params> for F
+where
+    Params: SystemParam + 'static,
+    F: SystemParamFunction<(), bool, Params, ()>,
+{
+    fn into_condition(self) -> Arc<Mutex<dyn Condition>>
+    where
+        Self: Sized,
+    {
+        Arc::new(Mutex::new(IntoSystem::into_system(self)))
+    }
+}
+
+// ========== FLAG CONDITIONS ==========
+
+/// A condition that passes when a flag is set (see [Flags::is_set])
+pub struct Flag(pub &'static str);
+impl Condition for Flag {
+    fn check(&mut self, world: &mut World) -> bool {
+        world.resource::<Flags>().is_set(self.0)
+    }
+    fn init(&mut self, _world: &mut World) {}
+}
+impl IntoCondition<Flag> for Flag {
+    fn into_condition(self) -> Arc<Mutex<dyn Condition>> {
+        Arc::new(Mutex::new(self))
+    }
+}
+
+/// A condition that passes when a flag isn't set (see [Flags::is_set])
+pub struct NotFlag(pub &'static str);
+impl Condition for NotFlag {
+    fn check(&mut self, world: &mut World) -> bool {
+        !world.resource::<Flags>().is_set(self.0)
+    }
+    fn init(&mut self, _world: &mut World) {}
+}
+impl IntoCondition<NotFlag> for NotFlag {
+    fn into_condition(self) -> Arc<Mutex<dyn Condition>> {
+        Arc::new(Mutex::new(self))
+    }
+}
+
+// ========== CONDITIONAL HANDLERS ==========
+
+/// An event handler, and the condition that has to pass for it to run
+#[derive(Clone)]
+pub struct ConditionalHandler {
+    pub(crate) condition: Option<Arc<Mutex<dyn Condition>>>,
+    pub(crate) handler: Arc<Mutex<dyn EventHandler>>,
+}
+
+/// The handlers for one event. Handlers with conditions are checked in the order they were
+/// added, then the handler without a condition (if there is one) runs if none of them passed.
+#[derive(Clone, Default)]
+pub struct HandlerList(pub(crate) Vec<ConditionalHandler>);
+impl HandlerList {
+    /// Add a handler. A handler without a condition replaces the existing unconditional handler,
+    /// if there is one; handlers with conditions are added before it.
+    pub(crate) fn add(
+        &mut self,
+        condition: Option<Arc<Mutex<dyn Condition>>>,
+        handler: Arc<Mutex<dyn EventHandler>>,
+    ) {
+        let unconditional = self.0.iter().position(|entry| entry.condition.is_none());
+        let entry = ConditionalHandler { condition, handler };
+        match (&entry.condition, unconditional) {
+            (None, Some(index)) => self.0[index] = entry,
+            (Some(_), Some(index)) => self.0.insert(index, entry),
+            (_, None) => self.0.push(entry),
+        }
+    }
+    /// The first handler whose condition passes.
+    ///
+    /// This runs the conditions, so it needs the world; clone the list out of its component first.
+    pub(crate) fn find(&self, world: &mut World) -> Option<Arc<Mutex<dyn EventHandler>>> {
+        self.0
+            .iter()
+            .find(|entry| match &entry.condition {
+                Some(condition) => condition.lock().unwrap().check(world),
+                None => true,
+            })
+            .map(|entry| entry.handler.clone())
+    }
+    /// If there aren't any handlers
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+}
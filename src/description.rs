@@ -0,0 +1,163 @@
+use crate::components::{Description, DynamicDescription, Room};
+use crate::flags::Flags;
+use bevy::ecs::system::{FunctionSystem, System, SystemParam, SystemParamFunction};
+use bevy::prelude::{Entity, IntoSystem, World};
+use std::sync::{Arc, Mutex};
+
+// ========== DESCRIPTION TRAITS ==========
+
+/// A generic trait to store systems that write descriptions.
+pub trait DescriptionSource: 'static + Sync + Send {
+    // Write the description
+    fn describe(&mut self, world: &mut World) -> String;
+    // Set up the DescriptionSource (FunctionSystems must be initialized before use)
+    fn init(&mut self, world: &mut World);
+}
+
+/// A generic trait to convert types into DescriptionSources.
+pub trait IntoDescription<Params>: Sync + Send + 'static {
+    // Convert the struct to a DescriptionSource
+    fn into_description(self) -> Arc<Mutex<dyn DescriptionSource>>
+    where
+        Self: Sized;
+}
+
+/// Implement [DescriptionSource] for [FunctionSystem]s that return a [String].
+impl<Fn, Params> DescriptionSource for FunctionSystem<(), String, Params, (), Fn>
+where
+    Params: SystemParam + 'static,
+    Fn: SystemParamFunction<(), String, Params, ()>,
+{
+    fn describe(&mut self, world: &mut World) -> String {
+        let description = self.run((), world);
+        self.apply_buffers(world);
+        description
+    }
+    fn init(&mut self, world: &mut World) {
+        self.initialize(world);
+    }
+}
+
+/// Implement [IntoDescription] for systems that return a [String].
+impl<Params, F> IntoDescription<Params> for F
+where
+    Params: SystemParam + 'static,
+    F: SystemParamFunction<(), String, Params, ()>,
+{
+    fn into_description(self) -> Arc<Mutex<dyn DescriptionSource>>
+    where
+        Self: Sized,
+    {
+        Arc::new(Mutex::new(IntoSystem::into_system(self)))
+    }
+}
+
+// ========== DESCRIBING ENTITIES ==========
+
+/// Get an entity's description, as the player would see it right now.
+///
+/// A [DynamicDescription] is used first, then a [Description], then a [Room]'s `description`.
+pub fn describe(world: &mut World, entity: Entity) -> Option<String> {
+    if let Some(dynamic) = world.get::<DynamicDescription>(entity).cloned() {
+        return Some(match dynamic {
+            DynamicDescription::System(source) => source.lock().unwrap().describe(world),
+            DynamicDescription::Variants { variants, default } => {
+                let flags = world.resource::<Flags>();
+                variants
+                    .into_iter()
+                    .find(|(flag, _)| flags.is_set(flag))
+                    .map_or(default, |(_, text)| text)
+            }
+        });
+    }
+    if let Some(description) = world.get::<Description>(entity) {
+        return Some(description.0.clone());
+    }
+    world
+        .get::<Room>(entity)
+        .and_then(|room| room.description)
+        .map(str::to_owned)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::components::Description;
+    use bevy::prelude::Res;
+
+    fn world() -> World {
+        let mut world = World::new();
+        world.init_resource::<Flags>();
+        world
+    }
+
+    fn fire(flags: Res<Flags>) -> String {
+        match flags.is_set("fire") {
+            true => "The fire crackles.".to_owned(),
+            false => "The fireplace is cold.".to_owned(),
+        }
+    }
+
+    #[test]
+    fn variants_use_the_first_set_flag() {
+        let mut world = world();
+        let entity = world
+            .spawn(
+                DynamicDescription::variants("You're alone.")
+                    .when("sarah", "Sarah is here.")
+                    .when("kids", "Your kids are here."),
+            )
+            .id();
+
+        assert_eq!(describe(&mut world, entity).unwrap(), "You're alone.");
+        world.resource_mut::<Flags>().set("kids", true);
+        assert_eq!(describe(&mut world, entity).unwrap(), "Your kids are here.");
+        // Variants added earlier win
+        world.resource_mut::<Flags>().set("sarah", true);
+        assert_eq!(describe(&mut world, entity).unwrap(), "Sarah is here.");
+        world.resource_mut::<Flags>().set("sarah", false);
+        assert_eq!(describe(&mut world, entity).unwrap(), "Your kids are here.");
+    }
+
+    #[test]
+    fn systems_write_descriptions() {
+        let mut world = world();
+        let description = DynamicDescription::new(fire);
+        if let DynamicDescription::System(source) = &description {
+            source.lock().unwrap().init(&mut world);
+        }
+        let entity = world.spawn(description).id();
+
+        assert_eq!(
+            describe(&mut world, entity).unwrap(),
+            "The fireplace is cold."
+        );
+        world.resource_mut::<Flags>().set("fire", true);
+        assert_eq!(describe(&mut world, entity).unwrap(), "The fire crackles.");
+    }
+
+    #[test]
+    fn dynamic_descriptions_come_first() {
+        let mut world = world();
+        let entity = world
+            .spawn((
+                Description("A plain description.".to_owned()),
+                DynamicDescription::variants("A dynamic description."),
+            ))
+            .id();
+        let room = world
+            .spawn(Room {
+                name: "Attic",
+                description: Some("A dusty attic."),
+            })
+            .id();
+        let nothing = world.spawn_empty().id();
+
+        assert_eq!(
+            describe(&mut world, entity).unwrap(),
+            "A dynamic description."
+        );
+        assert_eq!(describe(&mut world, room).unwrap(), "A dusty attic.");
+        assert_eq!(describe(&mut world, nothing), None);
+    }
+}
@@ -0,0 +1,40 @@
+use crate::events::EventHandler;
+use crate::rooms::RoomId;
+use bevy::prelude::{Resource, World};
+use std::fmt::{self, Display};
+use std::sync::{Arc, Mutex};
+
+/// Errors bevy_adventure reports while the game is running, instead of crashing.
+///
+/// These are sent as Bevy events, so any system can listen for them with an
+/// `EventReader<AdventureError>`. They're also printed to stderr, like
+/// [validation](crate::validation) problems, where the game's author will see them.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum AdventureError {
+    /// Tried to change to a room that doesn't exist. The player stays in their current room.
+    RoomNotFound(RoomId),
+}
+impl Display for AdventureError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::RoomNotFound(room) => write!(f, "the room `{}` does not exist", room),
+        }
+    }
+}
+impl std::error::Error for AdventureError {}
+
+/// The handler that runs after an [AdventureError] is reported, set with
+/// [AdventureApp::set_error_handler](crate::plugin::AdventureApp::set_error_handler).
+#[derive(Resource, Default)]
+pub struct ErrorHandler(pub(crate) Option<Arc<Mutex<dyn EventHandler>>>);
+
+/// Report an error: print it to stderr, send it as an event, then run the [ErrorHandler] (if there is one)
+pub fn report_error(world: &mut World, err: AdventureError) {
+    eprintln!("error: {}", err);
+    world.send_event(err);
+
+    let handler = world.resource::<ErrorHandler>().0.clone();
+    if let Some(handler) = handler {
+        handler.lock().unwrap().fire(world);
+    }
+}
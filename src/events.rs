@@ -1,3 +1,4 @@
+use crate::parser::InteractionContext;
 use bevy::prelude::World;
 use std::sync::{Arc, Mutex};
 
@@ -9,6 +10,11 @@ pub trait EventHandler: 'static + Sync + Send {
     fn fire(&mut self, world: &mut World);
     // Set up the EventHandler (FunctionSystems msut be initialized before use)
     fn init(&mut self, world: &mut World);
+    // Run the handler function for a player's interaction. Handlers that don't take an
+    // InteractionContext just ignore it.
+    fn fire_with(&mut self, world: &mut World, _context: InteractionContext) {
+        self.fire(world);
+    }
 }
 
 /// A generic trait to convert types into EventHandlers.
@@ -55,3 +61,48 @@ where
         Arc::new(Mutex::new(IntoSystem::into_system(self)))
     }
 }
+
+// ========== IMPL EVENT HANDLER FOR SYSTEMS TAKING AN INTERACTION CONTEXT ==========
+
+use bevy::ecs::system::InputMarker;
+use std::marker::PhantomData;
+
+/// Implement [EventHandler] for [FunctionSystem]s that take an `In<InteractionContext>`.
+///
+/// When fired outside of an interaction (for example, as an OnEnterRoom handler),
+/// the context is built from the player's last command.
+impl<Fn, Params> EventHandler for FunctionSystem<InteractionContext, (), Params, InputMarker, Fn>
+where
+    Params: SystemParam + 'static,
+    Fn: SystemParamFunction<InteractionContext, (), Params, InputMarker>,
+{
+    fn fire(&mut self, world: &mut World) {
+        let context = InteractionContext::from_world(world);
+        self.fire_with(world, context);
+    }
+    fn init(&mut self, world: &mut World) {
+        self.initialize(world);
+    }
+    fn fire_with(&mut self, world: &mut World, context: InteractionContext) {
+        self.run(context, world);
+        self.apply_buffers(world);
+    }
+}
+
+/// Marks [IntoEventHandler] impls for systems that take an `In<InteractionContext>`,
+/// so they don't overlap with the impl for regular systems.
+pub struct WithContext<Params>(PhantomData<Params>);
+
+/// Implement [IntoEventHandler] for systems that take an `In<InteractionContext>`.
+impl<Params, F> IntoEventHandler<WithContext<Params>> for F
+where
+    Params: SystemParam + 'static,
+    F: SystemParamFunction<InteractionContext, (), Params, InputMarker>,
+{
+    fn into_event(self) -> Arc<Mutex<dyn EventHandler>>
+    where
+        Self: Sized,
+    {
+        Arc::new(Mutex::new(IntoSystem::into_system(self)))
+    }
+}
@@ -0,0 +1,301 @@
+use crate::player::Checkpoints;
+use bevy::prelude::{Events, Mut, Resource, World};
+use bevy::utils::HashSet;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fmt::{self, Display};
+
+/// The value of one of the game's [Flags]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum FlagValue {
+    /// A flag that's on or off, like "metSarah"
+    Bool(bool),
+    /// A counter, like "daysTraveled"
+    Int(i64),
+    /// A variable, like "playerName"
+    Text(String),
+}
+impl FlagValue {
+    /// If this value counts as set: `true`, a non-zero number, or non-empty text
+    pub fn is_truthy(&self) -> bool {
+        match self {
+            Self::Bool(value) => *value,
+            Self::Int(value) => *value != 0,
+            Self::Text(value) => !value.is_empty(),
+        }
+    }
+}
+impl Display for FlagValue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Bool(value) => write!(f, "{}", value),
+            Self::Int(value) => write!(f, "{}", value),
+            Self::Text(value) => write!(f, "{}", value),
+        }
+    }
+}
+impl From<bool> for FlagValue {
+    fn from(value: bool) -> Self {
+        Self::Bool(value)
+    }
+}
+impl From<i64> for FlagValue {
+    fn from(value: i64) -> Self {
+        Self::Int(value)
+    }
+}
+impl From<i32> for FlagValue {
+    fn from(value: i32) -> Self {
+        Self::Int(value.into())
+    }
+}
+impl From<String> for FlagValue {
+    fn from(value: String) -> Self {
+        Self::Text(value)
+    }
+}
+impl From<&str> for FlagValue {
+    fn from(value: &str) -> Self {
+        Self::Text(value.to_owned())
+    }
+}
+
+/// Sent whenever one of the game's [Flags] changes.
+///
+/// Listen for these with an `EventReader<FlagChanged>`. Loading a save or undoing a command
+/// replaces every flag at once, and doesn't send these.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FlagChanged {
+    /// The flag's name
+    pub name: String,
+    /// What the flag was before (`None` if it wasn't set)
+    pub old: Option<FlagValue>,
+    /// What the flag is now (`None` if it was cleared)
+    pub new: Option<FlagValue>,
+}
+
+/// The game's state: on/off flags, counters, and text variables, all looked up by name.
+///
+/// ```ignore
+/// fn take_kids(mut flags: ResMut<Flags>) {
+///     flags.set("kids", true);
+///     flags.incr("captives", 2);
+///     flags.set("lastRoom", "Remove One");
+/// }
+/// ```
+///
+/// Boolean flags that are `true` also show up in [Checkpoints], and pushing to [Checkpoints]
+/// sets a boolean flag, so older games keep working. The two are kept in sync every frame.
+#[derive(Resource, Debug, Clone, Default, Serialize, Deserialize)]
+pub struct Flags {
+    values: BTreeMap<String, FlagValue>,
+    // Changes that haven't been sent as events yet
+    #[serde(skip)]
+    changes: Vec<FlagChanged>,
+}
+impl Flags {
+    /// Set a flag to a value: a [bool], an integer, or text
+    pub fn set(&mut self, name: &str, value: impl Into<FlagValue>) {
+        let value = value.into();
+        let old = self.values.insert(name.to_owned(), value.clone());
+        self.changed(name, old, Some(value));
+    }
+    /// Unset a flag. Returns what it used to be.
+    pub fn clear(&mut self, name: &str) -> Option<FlagValue> {
+        let old = self.values.remove(name);
+        if old.is_some() {
+            self.changed(name, old.clone(), None);
+        }
+        old
+    }
+    /// Flip a boolean flag, and return its new value. Flags that aren't set, or aren't booleans,
+    /// are treated as `false`, so they become `true`.
+    pub fn toggle(&mut self, name: &str) -> bool {
+        let value = !matches!(self.get(name), Some(FlagValue::Bool(true)));
+        self.set(name, value);
+        value
+    }
+    /// Add `amount` to a counter, and return its new value. Flags that aren't set, or aren't
+    /// integers, start from 0.
+    pub fn incr(&mut self, name: &str, amount: i64) -> i64 {
+        let value = self.int(name) + amount;
+        self.set(name, value);
+        value
+    }
+    /// A flag's value, if it's set
+    pub fn get(&self, name: &str) -> Option<&FlagValue> {
+        self.values.get(name)
+    }
+    /// If a flag is set to something truthy (see [FlagValue::is_truthy])
+    pub fn is_set(&self, name: &str) -> bool {
+        self.get(name).is_some_and(FlagValue::is_truthy)
+    }
+    /// A counter's value, or 0 if it isn't set or isn't an integer
+    pub fn int(&self, name: &str) -> i64 {
+        match self.get(name) {
+            Some(FlagValue::Int(value)) => *value,
+            _ => 0,
+        }
+    }
+    /// A text variable's value, if it's set to text
+    pub fn text(&self, name: &str) -> Option<&str> {
+        match self.get(name) {
+            Some(FlagValue::Text(value)) => Some(value),
+            _ => None,
+        }
+    }
+    /// Every flag that's set, and its value, in alphabetical order
+    pub fn iter(&self) -> impl Iterator<Item = (&str, &FlagValue)> {
+        self.values
+            .iter()
+            .map(|(name, value)| (name.as_str(), value))
+    }
+    /// Unset every flag
+    pub fn clear_all(&mut self) {
+        let names: Vec<String> = self.values.keys().cloned().collect();
+        for name in names {
+            self.clear(&name);
+        }
+    }
+
+    /// Replace every flag, without sending events (for loading & undoing)
+    pub(crate) fn replace(&mut self, flags: Flags) {
+        self.values = flags.values;
+    }
+
+    // Queue a FlagChanged event, if the value actually changed
+    fn changed(&mut self, name: &str, old: Option<FlagValue>, new: Option<FlagValue>) {
+        if old != new {
+            self.changes.push(FlagChanged {
+                name: name.to_owned(),
+                old,
+                new,
+            });
+        }
+    }
+}
+
+/// The [Checkpoints] as they were last synced with the [Flags]
+#[derive(Resource, Default)]
+pub(crate) struct SyncedCheckpoints {
+    view: Vec<&'static str>,
+    // Every checkpoint name seen so far. Checkpoints are `&'static str`s, so flags set by name
+    //  have to be leaked to show up in the view - this way, each name is only leaked once.
+    names: HashSet<&'static str>,
+}
+impl SyncedCheckpoints {
+    // The `&'static str` for a checkpoint name, leaking it if it hasn't been seen before
+    fn intern(&mut self, name: &str) -> &'static str {
+        match self.names.get(name) {
+            Some(interned) => interned,
+            None => {
+                let interned: &'static str = Box::leak(name.to_owned().into_boxed_str());
+                self.names.insert(interned);
+                interned
+            }
+        }
+    }
+}
+
+/// Keep [Checkpoints] in sync with [Flags], and send [FlagChanged] events
+pub(crate) fn update_flags(world: &mut World) {
+    sync_checkpoints(world);
+
+    let changes = std::mem::take(&mut world.resource_mut::<Flags>().changes);
+    let mut events = world.resource_mut::<Events<FlagChanged>>();
+    for change in changes {
+        events.send(change);
+    }
+}
+
+/// Apply anything the game pushed to or removed from [Checkpoints] to the [Flags], then
+/// rebuild [Checkpoints] from the [Flags]' true boolean flags
+pub(crate) fn sync_checkpoints(world: &mut World) {
+    world.resource_scope(|world, mut synced: Mut<SyncedCheckpoints>| {
+        let current = world.resource::<Checkpoints>().0.clone();
+        // Names the game pushed are already static, so they never need leaking
+        synced.names.extend(current.iter().copied());
+
+        // Changes made through Checkpoints
+        if current != synced.view {
+            let mut flags = world.resource_mut::<Flags>();
+            for checkpoint in current.iter().filter(|name| !synced.view.contains(name)) {
+                flags.set(checkpoint, true);
+            }
+            for checkpoint in synced.view.iter().filter(|name| !current.contains(name)) {
+                flags.clear(checkpoint);
+            }
+        }
+
+        // Keep the existing order, then add new checkpoints at the end
+        let flags = world.resource::<Flags>();
+        let mut view: Vec<&'static str> = current
+            .into_iter()
+            .filter(|name| matches!(flags.get(name), Some(FlagValue::Bool(true))))
+            .collect();
+        let view_len = view.len();
+        for (name, value) in flags.iter() {
+            if *value == FlagValue::Bool(true) && !view[..view_len].contains(&name) {
+                view.push(synced.intern(name));
+            }
+        }
+
+        if world.resource::<Checkpoints>().0 != view {
+            world.resource_mut::<Checkpoints>().0 = view.clone();
+        }
+        synced.view = view;
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn world() -> World {
+        let mut world = World::new();
+        world.init_resource::<Flags>();
+        world.init_resource::<SyncedCheckpoints>();
+        world.insert_resource(Checkpoints(Vec::new()));
+        world
+    }
+
+    #[test]
+    fn checkpoints_set_flags() {
+        let mut world = world();
+        world.resource_mut::<Checkpoints>().0.push("kids");
+        sync_checkpoints(&mut world);
+        assert!(world.resource::<Flags>().is_set("kids"));
+
+        world.resource_mut::<Checkpoints>().0.clear();
+        sync_checkpoints(&mut world);
+        assert_eq!(world.resource::<Flags>().get("kids"), None);
+    }
+
+    #[test]
+    fn flags_show_up_in_checkpoints() {
+        let mut world = world();
+        world.resource_mut::<Flags>().set("kids", true);
+        world.resource_mut::<Flags>().set("captives", 2);
+        sync_checkpoints(&mut world);
+        assert_eq!(world.resource::<Checkpoints>().0, vec!["kids"]);
+
+        world.resource_mut::<Flags>().set("kids", false);
+        sync_checkpoints(&mut world);
+        assert!(world.resource::<Checkpoints>().0.is_empty());
+    }
+
+    #[test]
+    fn flag_names_are_only_leaked_once() {
+        let mut world = world();
+        let mut seen = HashSet::default();
+        for _ in 0..10 {
+            world.resource_mut::<Flags>().set("door", true);
+            sync_checkpoints(&mut world);
+            seen.insert(world.resource::<Checkpoints>().0[0].as_ptr());
+
+            world.resource_mut::<Flags>().clear("door");
+            sync_checkpoints(&mut world);
+        }
+        assert_eq!(seen.len(), 1);
+    }
+}
@@ -0,0 +1,271 @@
+use super::{Transcript, TranscriptEntry};
+use std::fmt::{self, Display};
+use std::{fs, io, path::Path};
+
+/// Set this environment variable (to anything) to make
+/// [AdventureHarness::run_transcript](super::AdventureHarness::run_transcript)
+/// rewrite `.transcript` files with the game's actual output, instead of comparing against them.
+pub const UPDATE_TRANSCRIPTS_VAR: &str = "ADVENTURE_UPDATE_TRANSCRIPTS";
+
+/// The contents of a `.transcript` file.
+///
+/// A transcript file is plain text. Lines starting with `> ` are the player's inputs,
+/// and every other line is output the game is expected to print. For example:
+/// ```text
+/// You wake up to the sound of gunshots.
+/// (What do you do?) >
+/// > take kids
+/// You grab your kids.
+/// (What do you do?) >
+/// ```
+/// Trailing whitespace is ignored, so prompts can end in a bare `>`.
+/// Game output that starts with `> ` can't be represented, since it would be read as input.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TranscriptFile {
+    /// The player's inputs, in order
+    pub inputs: Vec<String>,
+    /// The output expected before the first input, between each pair of inputs, and after
+    /// the last input - so there is always one more output block than there are inputs.
+    pub outputs: Vec<String>,
+}
+impl TranscriptFile {
+    /// Parse a transcript from text
+    pub fn parse(text: &str) -> Self {
+        let mut inputs = Vec::new();
+        let mut outputs = Vec::new();
+        // Output lines since the last input
+        let mut block = Vec::new();
+
+        for line in text.lines() {
+            let input = match line.trim_end() {
+                ">" => Some(""),
+                _ => line.strip_prefix("> "),
+            };
+
+            if let Some(input) = input {
+                outputs.push(normalize(&block.join("\n")));
+                block.clear();
+                inputs.push(input.trim_end().to_owned());
+            } else {
+                block.push(line);
+            }
+        }
+        outputs.push(normalize(&block.join("\n")));
+
+        Self { inputs, outputs }
+    }
+    /// Build a transcript from a headless run. Inputs the game never asked for are
+    /// kept, with no output after them, so they aren't lost when the file is rewritten.
+    pub fn from_transcript(transcript: &Transcript) -> Self {
+        let mut inputs = Vec::new();
+        let mut outputs = vec![String::new()];
+
+        for entry in &transcript.entries {
+            match entry {
+                TranscriptEntry::Output(text) => outputs.last_mut().unwrap().push_str(text),
+                TranscriptEntry::Input(input) => {
+                    inputs.push(input.clone());
+                    outputs.push(String::new());
+                }
+            }
+        }
+        for input in &transcript.unused_inputs {
+            inputs.push(input.clone());
+            outputs.push(String::new());
+        }
+
+        Self {
+            inputs,
+            outputs: outputs.iter().map(|output| normalize(output)).collect(),
+        }
+    }
+    /// Read a `.transcript` file
+    pub fn load(path: impl AsRef<Path>) -> io::Result<Self> {
+        Ok(Self::parse(&fs::read_to_string(path)?))
+    }
+    /// Write this transcript to a file
+    pub fn save(&self, path: impl AsRef<Path>) -> io::Result<()> {
+        fs::write(path, self.to_string())
+    }
+    /// Compare `actual` against this (expected) transcript.
+    ///
+    /// Returns `None` if they match; otherwise, returns a line diff of every output
+    /// block that changed, labelled with the input that came before it.
+    pub fn diff(&self, actual: &Self) -> Option<String> {
+        let mut report = String::new();
+
+        if self.inputs != actual.inputs {
+            report.push_str("The inputs don't match:\n");
+            report.push_str(&diff_lines(
+                &self.inputs.join("\n"),
+                &actual.inputs.join("\n"),
+            ));
+        }
+
+        let blocks = self.outputs.len().max(actual.outputs.len());
+        for index in 0..blocks {
+            let expected = self.outputs.get(index).map_or("", String::as_str);
+            let found = actual.outputs.get(index).map_or("", String::as_str);
+            if expected == found {
+                continue;
+            }
+
+            // Label the block with the input that led to it
+            match index {
+                0 => report.push_str("\nBefore the first input:\n"),
+                _ => {
+                    let input = self.inputs.get(index - 1).map_or("", String::as_str);
+                    report.push_str(&format!("\nAfter input #{} `> {}`:\n", index, input));
+                }
+            }
+            report.push_str(&diff_lines(expected, found));
+        }
+
+        if report.is_empty() {
+            None
+        } else {
+            Some(report)
+        }
+    }
+}
+impl Display for TranscriptFile {
+    /// Write the transcript in the `.transcript` file format
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (index, output) in self.outputs.iter().enumerate() {
+            if !output.is_empty() {
+                writeln!(f, "{}", output)?;
+            }
+            if let Some(input) = self.inputs.get(index) {
+                writeln!(f, "> {}", input)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+// Remove trailing whitespace from each line, and trailing blank lines
+fn normalize(text: &str) -> String {
+    text.lines()
+        .map(str::trim_end)
+        .collect::<Vec<_>>()
+        .join("\n")
+        .trim_end()
+        .to_owned()
+}
+
+// A simple line-by-line diff (longest common subsequence), with `-` for expected lines
+// and `+` for actual lines
+fn diff_lines(expected: &str, actual: &str) -> String {
+    let expected: Vec<&str> = expected.lines().collect();
+    let actual: Vec<&str> = actual.lines().collect();
+
+    // lengths[i][j] = LCS length of expected[i..] and actual[j..]
+    let mut lengths = vec![vec![0usize; actual.len() + 1]; expected.len() + 1];
+    for i in (0..expected.len()).rev() {
+        for j in (0..actual.len()).rev() {
+            lengths[i][j] = if expected[i] == actual[j] {
+                lengths[i + 1][j + 1] + 1
+            } else {
+                lengths[i + 1][j].max(lengths[i][j + 1])
+            };
+        }
+    }
+
+    let mut diff = String::new();
+    let (mut i, mut j) = (0, 0);
+    while i < expected.len() || j < actual.len() {
+        if i < expected.len() && j < actual.len() && expected[i] == actual[j] {
+            diff.push_str(&format!("  {}\n", expected[i]));
+            i += 1;
+            j += 1;
+        } else if i < expected.len()
+            && (j == actual.len() || lengths[i + 1][j] >= lengths[i][j + 1])
+        {
+            diff.push_str(&format!("- {}\n", expected[i]));
+            i += 1;
+        } else {
+            diff.push_str(&format!("+ {}\n", actual[j]));
+            j += 1;
+        }
+    }
+    diff
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn diff_insert() {
+        assert_eq!(diff_lines("a\nc", "a\nb\nc"), "  a\n+ b\n  c\n");
+    }
+
+    #[test]
+    fn diff_delete() {
+        assert_eq!(diff_lines("a\nb\nc", "a\nc"), "  a\n- b\n  c\n");
+    }
+
+    #[test]
+    fn diff_change() {
+        assert_eq!(diff_lines("a\nb\nc", "a\nx\nc"), "  a\n- b\n+ x\n  c\n");
+    }
+
+    #[test]
+    fn diff_empty() {
+        assert_eq!(diff_lines("", "a"), "+ a\n");
+        assert_eq!(diff_lines("a", ""), "- a\n");
+        assert_eq!(diff_lines("", ""), "");
+    }
+
+    #[test]
+    fn parse_inputs_and_outputs() {
+        let file = TranscriptFile::parse("Hello\n(What do you do?) >\n> look\nA room.\n");
+        assert_eq!(file.inputs, vec!["look"]);
+        assert_eq!(file.outputs, vec!["Hello\n(What do you do?) >", "A room."]);
+    }
+
+    #[test]
+    fn parse_empty_file() {
+        let file = TranscriptFile::parse("");
+        assert!(file.inputs.is_empty());
+        assert_eq!(file.outputs, vec![""]);
+    }
+
+    #[test]
+    fn parse_malformed_lines() {
+        // A bare `>` is an empty input, `>` without a space is output, and CRLF line endings,
+        // trailing whitespace, and a missing final newline are all tolerated
+        let file = TranscriptFile::parse(">\r\n>look  \r\n> take kids \r\n\r\n> \r\nEnd   ");
+        assert_eq!(file.inputs, vec!["", "take kids", ""]);
+        assert_eq!(file.outputs, vec!["", ">look", "", "End"]);
+    }
+
+    #[test]
+    fn parse_round_trips() {
+        let text = "Hello\n> look\n> take kids\nYou grab your kids.\n";
+        let file = TranscriptFile::parse(text);
+        assert_eq!(file.to_string(), text);
+        assert_eq!(TranscriptFile::parse(&file.to_string()), file);
+    }
+
+    #[test]
+    fn diff_labels_changed_blocks() {
+        let expected = TranscriptFile::parse("Hello\n> look\nA room.\n");
+        let actual = TranscriptFile::parse("Hello\n> look\nA hall.\n");
+
+        assert_eq!(expected.diff(&expected), None);
+        let diff = expected.diff(&actual).unwrap();
+        assert!(diff.contains("After input #1 `> look`:"));
+        assert!(diff.contains("- A room.\n+ A hall.\n"));
+        assert!(!diff.contains("Before the first input"));
+    }
+
+    #[test]
+    fn diff_reports_changed_inputs() {
+        let expected = TranscriptFile::parse("> look\n");
+        let actual = TranscriptFile::parse("> look\n> take kids\n");
+
+        let diff = expected.diff(&actual).unwrap();
+        assert!(diff.starts_with("The inputs don't match:\n  look\n+ take kids\n"));
+    }
+}
@@ -0,0 +1,297 @@
+mod golden;
+
+use crate::events::IntoEventHandler;
+use crate::input_output_manager::{AdventureIo, IOManager};
+use crate::plugin::{AdventureApp, AdventurePlugin};
+use crate::save::SaveSettings;
+use bevy::app::{App, AppExit};
+use bevy::ecs::event::{Events, ManualEventReader};
+use bevy::prelude::IntoSystemDescriptor;
+use std::collections::VecDeque;
+use std::fmt::{self, Display};
+use std::panic::{self, AssertUnwindSafe};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use std::{env, fs, io, process};
+
+// ========== TRANSCRIPT ==========
+
+/// One piece of a [Transcript]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TranscriptEntry {
+    /// Text the game printed
+    Output(String),
+    /// A line the player typed (without the trailing newline)
+    Input(String),
+}
+
+/// Everything that happened during a headless run, in order.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Transcript {
+    /// The game's output & the player's input, in the order they happened
+    pub entries: Vec<TranscriptEntry>,
+    /// Scripted inputs the game never asked for
+    pub unused_inputs: Vec<String>,
+}
+impl Transcript {
+    /// All of the game's output joined together, without the player's input
+    pub fn output(&self) -> String {
+        self.entries
+            .iter()
+            .filter_map(|entry| match entry {
+                TranscriptEntry::Output(text) => Some(text.as_str()),
+                TranscriptEntry::Input(_) => None,
+            })
+            .collect()
+    }
+    /// Check if the game printed `text` at any point
+    pub fn contains(&self, text: &str) -> bool {
+        self.output().contains(text)
+    }
+}
+impl Display for Transcript {
+    /// Print the transcript the way it would look in a terminal
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for entry in &self.entries {
+            match entry {
+                TranscriptEntry::Output(text) => write!(f, "{}", text)?,
+                TranscriptEntry::Input(text) => writeln!(f, "{}", text)?,
+            }
+        }
+        Ok(())
+    }
+}
+
+// ========== SCRIPTED IO BACKEND ==========
+
+/// The panic payload [ScriptedIo] unwinds with once it runs out of input.
+///
+/// [AdventureHarness] catches this to stop the game; other panics are passed through.
+#[derive(Debug)]
+pub struct InputExhausted;
+
+#[derive(Default)]
+struct ScriptState {
+    inputs: VecDeque<String>,
+    transcript: Vec<TranscriptEntry>,
+}
+
+/// An in-memory [AdventureIo] backend that reads from a list of inputs and
+/// records everything into a [Transcript].
+///
+/// ScriptedIo is cheap to clone, and clones share the same script - so you can
+/// keep a copy to read the transcript after handing one to an [IOManager].
+#[derive(Clone, Default)]
+pub struct ScriptedIo(Arc<Mutex<ScriptState>>);
+impl ScriptedIo {
+    /// Make a new ScriptedIo that will answer prompts with `inputs`, in order
+    pub fn new<I, S>(inputs: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        Self(Arc::new(Mutex::new(ScriptState {
+            inputs: inputs.into_iter().map(Into::into).collect(),
+            transcript: Vec::new(),
+        })))
+    }
+    /// Add more inputs to the end of the script
+    pub fn push_input(&self, input: impl Into<String>) {
+        self.0.lock().unwrap().inputs.push_back(input.into());
+    }
+    /// Get everything recorded so far
+    pub fn transcript(&self) -> Transcript {
+        let state = self.0.lock().unwrap();
+        Transcript {
+            entries: state.transcript.clone(),
+            unused_inputs: state.inputs.iter().cloned().collect(),
+        }
+    }
+}
+impl AdventureIo for ScriptedIo {
+    fn read_line(&self) -> String {
+        let mut state = self.0.lock().unwrap();
+        match state.inputs.pop_front() {
+            Some(input) => {
+                state.transcript.push(TranscriptEntry::Input(input.clone()));
+                input + "\n"
+            }
+            None => {
+                // Release the lock first, so unwinding doesn't poison it
+                drop(state);
+                // resume_unwind skips the panic hook, so nothing gets printed
+                panic::resume_unwind(Box::new(InputExhausted))
+            }
+        }
+    }
+    fn write(&self, text: &str) {
+        let mut state = self.0.lock().unwrap();
+        // Merge consecutive writes, since IOManager prints one character at a time
+        if let Some(TranscriptEntry::Output(output)) = state.transcript.last_mut() {
+            output.push_str(text);
+        } else {
+            state
+                .transcript
+                .push(TranscriptEntry::Output(text.to_owned()));
+        }
+    }
+    fn clear(&self) {}
+}
+
+// ========== HARNESS ==========
+
+/// Runs a game headlessly, with scripted player input, and returns what was printed.
+///
+/// The harness builds an [App] with [AdventurePlugin], swaps the [IOManager] over to a
+/// [ScriptedIo] with no typing delays, and keeps saves in a temporary directory of its own. Then it
+/// updates the app until the game quits, runs out of input, or hits the update limit.
+///
+/// Example:
+/// ```ignore
+/// #[test]
+/// fn take_kids() {
+///     let transcript = AdventureHarness::new(["yes", "take kids", "leave", "forwards"])
+///         .add_world_builder(build_opening)
+///         .add_world_builder(invasion::build)
+///         .run();
+///
+///     assert!(transcript.contains("You grab your kids."));
+/// }
+/// ```
+pub struct AdventureHarness {
+    app: App,
+    io: ScriptedIo,
+    max_updates: usize,
+}
+impl AdventureHarness {
+    /// Make a new harness that will answer prompts with `inputs`, in order
+    pub fn new<I, S>(inputs: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        let io = ScriptedIo::new(inputs);
+
+        let mut iomgr = IOManager::with_io(io.clone());
+        iomgr.set_delays(Duration::ZERO, Duration::ZERO, Duration::ZERO);
+
+        let mut app = App::new();
+        app.add_plugin(AdventurePlugin::new("adventure_harness"))
+            // Replace the terminal IOManager the plugin added
+            .insert_resource(iomgr)
+            // Keep test saves out of the player's data directory
+            .insert_resource(SaveSettings::in_directory(save_directory()));
+
+        Self {
+            app,
+            io,
+            max_updates: 1000,
+        }
+    }
+    /// Add a startup system to the app. Systems that build your game's rooms should use
+    /// [AdventureHarness::add_world_builder] instead, so the game can be restarted.
+    pub fn add_startup_system<Params>(mut self, system: impl IntoSystemDescriptor<Params>) -> Self {
+        self.app.add_startup_system(system);
+        self
+    }
+    /// Add a world builder to the app (see [AdventureApp::add_world_builder])
+    pub fn add_world_builder<Params>(mut self, builder: impl IntoEventHandler<Params>) -> Self {
+        self.app.add_world_builder(builder);
+        self
+    }
+    /// Get the app, to add anything else the game needs
+    pub fn app(&mut self) -> &mut App {
+        &mut self.app
+    }
+    /// The most times the app will be updated before the harness gives up (default: 1000)
+    pub fn max_updates(mut self, max_updates: usize) -> Self {
+        self.max_updates = max_updates;
+        self
+    }
+    /// Run the game until it quits, runs out of input, or hits [AdventureHarness::max_updates]
+    pub fn run(mut self) -> Transcript {
+        let mut exit_reader = ManualEventReader::<AppExit>::default();
+
+        for _ in 0..self.max_updates {
+            let update = panic::catch_unwind(AssertUnwindSafe(|| self.app.update()));
+
+            match update {
+                Ok(()) => {
+                    // Stop if the game quit
+                    let exit_events = self.app.world.resource::<Events<AppExit>>();
+                    if exit_reader.iter(exit_events).last().is_some() {
+                        break;
+                    }
+                }
+                // Stop if the game asked for more input than was scripted
+                Err(payload) if payload.is::<InputExhausted>() => break,
+                // Any other panic is a real error
+                Err(payload) => panic::resume_unwind(payload),
+            }
+        }
+
+        self.io.transcript()
+    }
+    /// Replay the inputs from a `.transcript` file, and panic with a diff if the game's
+    /// output doesn't match the file. See [TranscriptFile] for the file format.
+    ///
+    /// If the [UPDATE_TRANSCRIPTS_VAR] environment variable is set, the file is rewritten
+    /// with the game's actual output instead (and created, if it doesn't exist yet):
+    /// ```text
+    /// ADVENTURE_UPDATE_TRANSCRIPTS=1 cargo test
+    /// ```
+    pub fn run_transcript(self, path: impl AsRef<Path>) {
+        let path = path.as_ref();
+        let update = env::var_os(UPDATE_TRANSCRIPTS_VAR).is_some();
+
+        let expected = match TranscriptFile::load(path) {
+            Ok(expected) => expected,
+            Err(err) if update && err.kind() == io::ErrorKind::NotFound => {
+                TranscriptFile::parse("")
+            }
+            Err(err) => panic!("Couldn't read transcript `{}`: {}", path.display(), err),
+        };
+
+        // Feed the file's inputs to the game
+        for input in &expected.inputs {
+            self.io.push_input(input.clone());
+        }
+        let actual = TranscriptFile::from_transcript(&self.run());
+
+        if update {
+            if actual != expected {
+                actual.save(path).unwrap_or_else(|err| {
+                    panic!("Couldn't write transcript `{}`: {}", path.display(), err)
+                });
+            }
+        } else if let Some(diff) = expected.diff(&actual) {
+            panic!(
+                "Transcript `{}` doesn't match the game's output:\n{}\nRerun with {}=1 to update it.",
+                path.display(),
+                diff,
+                UPDATE_TRANSCRIPTS_VAR
+            );
+        }
+    }
+}
+
+// A new, empty save directory for each harness, so tests running in parallel don't share save slots
+fn save_directory() -> PathBuf {
+    static HARNESSES: AtomicUsize = AtomicUsize::new(0);
+    let directory = env::temp_dir()
+        .join("adventure_harness")
+        .join(format!(
+            "{}-{}",
+            process::id(),
+            HARNESSES.fetch_add(1, Ordering::Relaxed)
+        ))
+        .join("saves");
+    // Clear out anything left by an earlier process with the same ID
+    let _ = fs::remove_dir_all(&directory);
+    directory
+}
+
+// Re-exports
+pub use golden::{TranscriptFile, UPDATE_TRANSCRIPTS_VAR};
@@ -0,0 +1,53 @@
+use std::io::{stdin, stdout, Write};
+
+/// The backend [IOManager](super::IOManager) reads from & writes to.
+///
+/// [IOManager](super::IOManager) handles the text effects (typing delays, prompts, etc), then
+/// passes the raw text to its backend. Implement this trait to run a game somewhere
+/// other than the terminal - for example, in memory, over a socket, or in a TUI.
+///
+/// IOManager is used through `Res<IOManager>`, so these methods only take `&self`;
+/// backends that need to mutate state should use interior mutability.
+pub trait AdventureIo: Send + Sync + 'static {
+    /// Read one line of input from the player (including the trailing newline, if any)
+    fn read_line(&self) -> String;
+    /// Write text to the player, with no newline at the end
+    fn write(&self, text: &str);
+    /// Force any buffered output to be shown to the player
+    fn flush(&self) {}
+    /// Clear all output shown to the player
+    fn clear(&self);
+    /// Hide the player's cursor
+    fn hide_cursor(&self) {}
+    /// Show the player's cursor
+    fn show_cursor(&self) {}
+}
+
+/// The default [AdventureIo] backend - reads from stdin and writes to stdout.
+///
+/// Uses ANSI escape codes to clear the screen and hide/show the cursor.
+#[derive(Default)]
+pub struct TerminalIo;
+
+impl AdventureIo for TerminalIo {
+    fn read_line(&self) -> String {
+        let mut input = String::new();
+        stdin().read_line(&mut input).expect("Error reading stdIn!");
+        input
+    }
+    fn write(&self, text: &str) {
+        print!("{}", text);
+    }
+    fn flush(&self) {
+        stdout().flush().expect("Error flushing stdout!");
+    }
+    fn clear(&self) {
+        print!("\x1B[2J");
+    }
+    fn hide_cursor(&self) {
+        print!("\x1B[?25l");
+    }
+    fn show_cursor(&self) {
+        print!("\x1B[?25h");
+    }
+}
@@ -1,8 +1,5 @@
 use super::IOManager;
-use std::{
-    io::{stdin, stdout, Write},
-    str::FromStr,
-};
+use std::str::FromStr;
 
 // Input from player
 impl IOManager {
@@ -10,18 +7,17 @@ impl IOManager {
     pub fn prompt_raw(&self, prompt_text: &str) -> String {
         // Check if the cursor is hidden - if it is, we should show it
         if self.cursor_hidden {
-            Self::temp_show_cursor();
+            self.temp_show_cursor();
         }
 
-        print!("({}) > ", prompt_text);
-        stdout().flush().expect("Error flushing stdOut!");
+        self.io.write(&format!("({}) > ", prompt_text));
+        self.io.flush();
 
-        let mut input = String::new();
-        stdin().read_line(&mut input).expect("Error reading stdIn!");
+        let input = self.io.read_line();
 
         // If we need to re-hide the cursor
         if self.cursor_hidden {
-            Self::temp_hide_cursor();
+            self.temp_hide_cursor();
         }
 
         // Trim player input and remove punctuation
@@ -82,10 +78,10 @@ impl IOManager {
             "Choose 1",
             "Please type the number of the option you want.",
             |test_choice: &u8| {
-                if usize::from(test_choice - 1) < choices.len() {
-                    Some(*test_choice)
-                } else {
-                    None
+                // Options start at 1, so 0 isn't one of them
+                match test_choice.checked_sub(1) {
+                    Some(index) if usize::from(index) < choices.len() => Some(*test_choice),
+                    _ => None,
                 }
             },
         )
@@ -1,3 +1,4 @@
+use super::vocabulary::VerbId;
 use phf::phf_map;
 
 /// The type of action detected from different keywords.
@@ -5,6 +6,7 @@ use phf::phf_map;
 pub enum WordType {
     Look,
     Take,
+    Drop,
     Talk,
     Use,
     Open,
@@ -15,8 +17,47 @@ pub enum WordType {
     Ignore,
     PotentialTarget,
     Any,
+    /// An action added by the game, rather than bevy_adventure (see [Vocabulary](super::Vocabulary))
+    Custom(VerbId),
 }
+impl WordType {
+    /// The built-in actions, in the order they're listed by the `help` command
+    pub const BUILT_IN_ACTIONS: [WordType; 10] = [
+        WordType::Look,
+        WordType::Take,
+        WordType::Drop,
+        WordType::Talk,
+        WordType::Use,
+        WordType::Open,
+        WordType::Break,
+        WordType::Move,
+        WordType::Eat,
+        WordType::Give,
+    ];
 
+    /// A lowercase name for this type of word. Custom verbs use their [VerbId]'s name.
+    pub fn name(&self) -> &'static str {
+        match self {
+            WordType::Look => "look",
+            WordType::Take => "take",
+            WordType::Drop => "drop",
+            WordType::Talk => "talk",
+            WordType::Use => "use",
+            WordType::Open => "open",
+            WordType::Break => "break",
+            WordType::Move => "move",
+            WordType::Eat => "eat",
+            WordType::Give => "give",
+            WordType::Ignore => "ignore",
+            WordType::PotentialTarget => "target",
+            WordType::Any => "any",
+            WordType::Custom(verb) => verb.0,
+        }
+    }
+}
+
+/// The built-in keywords. The [Vocabulary](super::Vocabulary) resource starts out with these,
+/// and is what the game actually uses, so games can add their own words.
 pub const KEYWORDS: phf::Map<&'static str, WordType> = phf_map! {
     "look" => WordType::Look,
     "observe" => WordType::Look,
@@ -36,6 +77,9 @@ pub const KEYWORDS: phf::Map<&'static str, WordType> = phf_map! {
     "steal" => WordType::Take,
     "get" => WordType::Take,
 
+    "drop" => WordType::Drop,
+    "discard" => WordType::Drop,
+
     "give" => WordType::Give,
     "hand" => WordType::Give,
 
@@ -53,4 +97,9 @@ pub const KEYWORDS: phf::Map<&'static str, WordType> = phf_map! {
     "enter" => WordType::Move,
     "leave" => WordType::Move,
     "exit" => WordType::Move,
+
+    // Stop words (just the articles - games can add their own with `Vocabulary::add_stop_word`)
+    "the" => WordType::Ignore,
+    "a" => WordType::Ignore,
+    "an" => WordType::Ignore,
 };
@@ -1,6 +1,8 @@
+mod backend;
 mod input;
 mod keywords;
 mod output;
+mod vocabulary;
 
 use bevy::prelude::Resource;
 use bevy::utils::HashMap;
@@ -16,11 +18,18 @@ pub struct IOManager {
     pub(crate) medium_delay: Duration,
     pub(crate) long_delay: Duration,
     pub(crate) autoprompt: bool,
+    // The room's description was just printed, so AutoPrompt shouldn't print it again
+    pub(crate) room_described: bool,
     pub(crate) punctuation: HashMap<char, Duration>,
+    pub(crate) io: Box<dyn AdventureIo>,
 }
 impl IOManager {
-    /// Make a new IOManager instance
+    /// Make a new IOManager instance, using the terminal for input & output
     pub fn new() -> Self {
+        Self::with_io(TerminalIo)
+    }
+    /// Make a new IOManager instance that uses a custom [AdventureIo] backend
+    pub fn with_io(io: impl AdventureIo) -> Self {
         let long_delay = Duration::from_millis(500);
         let medium_delay = Duration::from_millis(200);
         let short_delay = Duration::from_millis(50);
@@ -33,18 +42,38 @@ impl IOManager {
             medium_delay,
             long_delay,
             autoprompt: false,
+            room_described: false,
             punctuation,
+            io: Box::new(io),
         };
-
-        this.punctuation.insert(',', this.medium_delay);
-        this.punctuation.insert(';', this.medium_delay);
-        this.punctuation.insert('.', this.long_delay);
-        this.punctuation.insert('?', this.long_delay);
-        this.punctuation.insert('!', this.long_delay);
-        this.punctuation.insert('\n', this.long_delay);
+        this.update_punctuation();
 
         this
     }
+    /// Change how long output waits after each character.
+    ///
+    /// `short` is used after normal characters, `medium` after commas & semicolons, and
+    /// `long` after sentence endings, newlines, and each line of [IOManager::printlns].
+    /// Pass [Duration::ZERO] for all three to print instantly.
+    pub fn set_delays(&mut self, short: Duration, medium: Duration, long: Duration) {
+        self.short_delay = short;
+        self.medium_delay = medium;
+        self.long_delay = long;
+        self.update_punctuation();
+    }
+    // Map punctuation characters to their delays
+    fn update_punctuation(&mut self) {
+        self.punctuation.insert(',', self.medium_delay);
+        self.punctuation.insert(';', self.medium_delay);
+        self.punctuation.insert('.', self.long_delay);
+        self.punctuation.insert('?', self.long_delay);
+        self.punctuation.insert('!', self.long_delay);
+        self.punctuation.insert('\n', self.long_delay);
+    }
+    /// Swap out the [AdventureIo] backend this IOManager uses
+    pub fn set_io(&mut self, io: impl AdventureIo) {
+        self.io = Box::new(io);
+    }
 }
 impl Default for IOManager {
     /// Make a new IOManager this with the Default trait
@@ -54,4 +83,6 @@ impl Default for IOManager {
 }
 
 // Re-exports
+pub use backend::{AdventureIo, TerminalIo};
 pub use keywords::{WordType, KEYWORDS};
+pub use vocabulary::{VerbId, Vocabulary};
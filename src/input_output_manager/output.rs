@@ -1,13 +1,5 @@
 use super::IOManager;
-use std::{
-    fmt::Display,
-    io::{stdout, Write},
-    thread,
-};
-
-fn flush() {
-    stdout().flush().expect("Error flushing stdout!");
-}
+use std::{fmt::Display, thread};
 
 // Output to player
 impl IOManager {
@@ -15,9 +7,9 @@ impl IOManager {
     pub fn print<F: ToString>(&self, text: F) {
         // Print each character one at a time
         text.to_string().chars().for_each(|char| {
-            print!("{}", char);
-            // Force stdout to refresh
-            flush();
+            self.io.write(char.encode_utf8(&mut [0; 4]));
+            // Force the output to refresh
+            self.io.flush();
             // Delay between each character
             if let Some(delay) = self.punctuation.get(&char) {
                 // Bigger delay for punctuation
@@ -31,42 +23,43 @@ impl IOManager {
     /// For printing text with a newline at the end
     pub fn println<F: ToString>(&self, text: F) {
         self.print(text);
-        println!();
+        self.io.write("\n");
+        self.io.flush();
     }
     /// For printing multiple lines of text one after another (intended for lists)
     pub fn printlns<F: Display>(&self, text: Vec<F>) {
         // Print each line one at a time
         text.into_iter().for_each(|line| {
             // Print the line
-            println!("{}", line);
-            // Force stdout to refresh
-            flush();
+            self.io.write(&format!("{}\n", line));
+            // Force the output to refresh
+            self.io.flush();
             // Delay between each line
             thread::sleep(self.long_delay);
         })
     }
-    /// For clearing output - uses ANSI escape codes to clear the terminal window
+    /// For clearing output - the terminal backend uses ANSI escape codes to clear the window
     pub fn clear(&self) {
-        print!("\x1B[2J");
+        self.io.clear();
     }
     /// Temporarily hide the cursor (doesn't update `self.cursor_hidden`)
-    pub fn temp_hide_cursor() {
-        print!("\x1B[?25l");
+    pub fn temp_hide_cursor(&self) {
+        self.io.hide_cursor();
     }
     /// Temporarily show the cursor (doesn't update `self.cursor_hidden`)
-    pub fn temp_show_cursor() {
-        print!("\x1B[?25h");
+    pub fn temp_show_cursor(&self) {
+        self.io.show_cursor();
     }
-    /// Uses ANSI escape codes to hide the terminal cursor
+    /// Hide the player's cursor (the terminal backend uses ANSI escape codes)
     pub fn hide_cursor(&mut self) {
-        Self::temp_hide_cursor();
+        self.temp_hide_cursor();
 
         // Update hide_cursor
         self.cursor_hidden = true;
     }
-    /// Uses ANSI escape codes to show the terminal cursor
+    /// Show the player's cursor (the terminal backend uses ANSI escape codes)
     pub fn show_cursor(&mut self) {
-        Self::temp_show_cursor();
+        self.temp_show_cursor();
 
         // Update hide_cursor
         self.cursor_hidden = false;
@@ -0,0 +1,262 @@
+use super::keywords::{WordType, KEYWORDS};
+use bevy::prelude::{Component, Resource};
+use bevy::utils::HashMap;
+
+/// The words the player can use for actions, and the [WordType] each one means.
+///
+/// The `Vocabulary` resource starts out with the built-in [KEYWORDS], and games can
+/// add to it at startup:
+/// ```ignore
+/// fn add_words(mut vocabulary: ResMut<Vocabulary>) {
+///     // A synonym for a built-in action
+///     vocabulary.add_synonym("inspect", WordType::Look);
+///     // A new action, which can be bound with `on_interact(WordType::Custom(PRAY), ...)`
+///     vocabulary.add_verb(PRAY, &["pray", "worship"]);
+///     // A word to skip
+///     vocabulary.add_stop_word("yonder");
+/// }
+/// ```
+///
+/// A `Vocabulary` can also be added to a room as a component. While that room is active,
+/// its words are checked before the game's, so they can add or override words in just that room.
+/// Use [Vocabulary::empty] for these, so they don't repeat every built-in keyword.
+#[derive(Resource, Component, Debug, Clone)]
+pub struct Vocabulary {
+    words: HashMap<String, WordType>,
+    // Custom verbs, in the order they were added
+    verbs: Vec<VerbId>,
+}
+impl Vocabulary {
+    /// A vocabulary with no words in it
+    pub fn empty() -> Self {
+        Self {
+            words: HashMap::new(),
+            verbs: Vec::new(),
+        }
+    }
+    /// A vocabulary with all of the built-in [KEYWORDS]
+    pub fn built_in() -> Self {
+        Self {
+            words: KEYWORDS
+                .entries()
+                .map(|(word, word_type)| (word.to_string(), word_type.clone()))
+                .collect(),
+            verbs: Vec::new(),
+        }
+    }
+    /// Make `word` mean `word_type`, replacing whatever it meant before. Words are matched ignoring case.
+    pub fn add_synonym(&mut self, word: &str, word_type: WordType) -> &mut Self {
+        if let WordType::Custom(verb) = word_type {
+            if !self.verbs.contains(&verb) {
+                self.verbs.push(verb);
+            }
+        }
+        self.words.insert(word.to_lowercase(), word_type);
+        self
+    }
+    /// Add a custom verb, and the words the player can use for it.
+    /// Custom verbs are listed by the `help` command after the built-in ones, in the order they're added.
+    pub fn add_verb(&mut self, verb: VerbId, words: &[&str]) -> &mut Self {
+        for word in words {
+            self.add_synonym(word, WordType::Custom(verb));
+        }
+        self
+    }
+    /// Add a stop word - a word the parser skips entirely, like "the" or "an".
+    /// This is the same as adding a synonym for [WordType::Ignore].
+    pub fn add_stop_word(&mut self, word: &str) -> &mut Self {
+        self.add_synonym(word, WordType::Ignore)
+    }
+    /// The same as [Vocabulary::add_synonym], but takes and returns `self`, for building room vocabularies
+    pub fn with_synonym(mut self, word: &str, word_type: WordType) -> Self {
+        self.add_synonym(word, word_type);
+        self
+    }
+    /// The same as [Vocabulary::add_verb], but takes and returns `self`, for building room vocabularies
+    pub fn with_verb(mut self, verb: VerbId, words: &[&str]) -> Self {
+        self.add_verb(verb, words);
+        self
+    }
+    /// Stop recognizing a word. Returns what it used to mean.
+    pub fn remove(&mut self, word: &str) -> Option<WordType> {
+        self.words.remove(&word.to_lowercase())
+    }
+    /// What a word means, if it's in this vocabulary
+    pub fn get(&self, word: &str) -> Option<&WordType> {
+        self.words.get(&word.to_lowercase())
+    }
+    /// Every word that means `word_type`, in alphabetical order
+    pub fn words_for(&self, word_type: &WordType) -> Vec<&str> {
+        let mut words: Vec<&str> = self
+            .words
+            .iter()
+            .filter(|(_, meaning)| *meaning == word_type)
+            .map(|(word, _)| word.as_str())
+            .collect();
+        words.sort_unstable();
+        words
+    }
+    /// Every word in this vocabulary, and what it means
+    pub fn iter(&self) -> impl Iterator<Item = (&str, &WordType)> {
+        self.words
+            .iter()
+            .map(|(word, meaning)| (word.as_str(), meaning))
+    }
+    /// Every action the player can take with these vocabularies, and the words for each one.
+    ///
+    /// Built-in actions come first, then custom verbs in the order they were added.
+    /// A word is only listed under the first meaning found for it (see [Vocabulary::lookup]).
+    pub fn actions<'a>(vocabularies: &[&'a Self]) -> Vec<(WordType, Vec<&'a str>)> {
+        let mut actions: Vec<WordType> = WordType::BUILT_IN_ACTIONS.to_vec();
+        for vocabulary in vocabularies {
+            for verb in &vocabulary.verbs {
+                if !actions.contains(&WordType::Custom(*verb)) {
+                    actions.push(WordType::Custom(*verb));
+                }
+            }
+        }
+
+        actions
+            .into_iter()
+            .map(|action| {
+                let mut words: Vec<&'a str> = Vec::new();
+                for vocabulary in vocabularies {
+                    for word in vocabulary.words_for(&action) {
+                        if !words.contains(&word)
+                            && Self::lookup(vocabularies, word) == Some(&action)
+                        {
+                            words.push(word);
+                        }
+                    }
+                }
+                words.sort_unstable();
+                (action, words)
+            })
+            .filter(|(_, words)| !words.is_empty())
+            .collect()
+    }
+    /// Look a word up in several vocabularies, in order, returning the first meaning found
+    pub fn lookup<'a>(vocabularies: &[&'a Self], word: &str) -> Option<&'a WordType> {
+        vocabularies
+            .iter()
+            .find_map(|vocabulary| vocabulary.get(word))
+    }
+}
+impl Default for Vocabulary {
+    /// The built-in vocabulary (see [Vocabulary::built_in])
+    fn default() -> Self {
+        Self::built_in()
+    }
+}
+
+/// Identifies a custom verb - an action that isn't one of the built-in [WordType]s.
+///
+/// Declare custom verbs as constants, then use them with [WordType::Custom]:
+/// ```ignore
+/// const PRAY: VerbId = VerbId("pray");
+///
+/// commands.spawn(Name("altar")).on_interact(WordType::Custom(PRAY), pray_at_altar);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct VerbId(pub &'static str);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn synonyms() {
+        let mut vocabulary = Vocabulary::built_in();
+        vocabulary.add_synonym("Inspect", WordType::Look);
+        assert_eq!(vocabulary.get("inspect"), Some(&WordType::Look));
+        assert_eq!(vocabulary.get("INSPECT"), Some(&WordType::Look));
+
+        // Synonyms replace what a word meant before
+        vocabulary.add_synonym("look", WordType::Take);
+        assert_eq!(vocabulary.get("look"), Some(&WordType::Take));
+    }
+
+    #[test]
+    fn remove_words() {
+        let mut vocabulary = Vocabulary::built_in();
+        assert_eq!(vocabulary.remove("Steal"), Some(WordType::Take));
+        assert_eq!(vocabulary.get("steal"), None);
+        assert_eq!(vocabulary.remove("steal"), None);
+        // Other words for the same action are still there
+        assert_eq!(vocabulary.get("take"), Some(&WordType::Take));
+    }
+
+    #[test]
+    fn room_vocabularies_go_first() {
+        let game = Vocabulary::built_in();
+        let room = Vocabulary::empty()
+            .with_synonym("open", WordType::Break)
+            .with_synonym("pry", WordType::Open);
+
+        let vocabularies = [&room, &game];
+        assert_eq!(
+            Vocabulary::lookup(&vocabularies, "open"),
+            Some(&WordType::Break)
+        );
+        assert_eq!(
+            Vocabulary::lookup(&vocabularies, "pry"),
+            Some(&WordType::Open)
+        );
+        // Anything the room doesn't know falls back to the game's words
+        assert_eq!(
+            Vocabulary::lookup(&vocabularies, "take"),
+            Some(&WordType::Take)
+        );
+        assert_eq!(Vocabulary::lookup(&vocabularies, "fly"), None);
+    }
+
+    const PRAY: VerbId = VerbId("pray");
+    const DANCE: VerbId = VerbId("dance");
+
+    #[test]
+    fn custom_verbs() {
+        let mut vocabulary = Vocabulary::built_in();
+        vocabulary.add_verb(PRAY, &["pray", "Worship"]);
+
+        assert_eq!(vocabulary.get("worship"), Some(&WordType::Custom(PRAY)));
+        assert_eq!(
+            vocabulary.words_for(&WordType::Custom(PRAY)),
+            vec!["pray", "worship"]
+        );
+        assert_eq!(WordType::Custom(PRAY).name(), "pray");
+    }
+
+    #[test]
+    fn actions_are_listed_in_order() {
+        let mut game = Vocabulary::built_in();
+        game.add_verb(PRAY, &["pray", "worship"]);
+        game.add_verb(DANCE, &["dance"]);
+        // In this room, "worship" means something else
+        let room = Vocabulary::empty().with_synonym("worship", WordType::Look);
+
+        let actions = Vocabulary::actions(&[&room, &game]);
+        let names: Vec<&str> = actions.iter().map(|(action, _)| action.name()).collect();
+        // Built-in actions first, then custom verbs in the order they were added
+        assert_eq!(
+            names,
+            vec![
+                "look", "take", "drop", "talk", "use", "open", "break", "move", "eat", "give",
+                "pray", "dance"
+            ]
+        );
+
+        let words = |action: &WordType| {
+            actions
+                .iter()
+                .find(|(test, _)| test == action)
+                .map(|(_, words)| words.clone())
+                .unwrap()
+        };
+        assert_eq!(
+            words(&WordType::Look),
+            vec!["examine", "look", "observe", "study", "worship"]
+        );
+        // "worship" is only listed under what it means in this room
+        assert_eq!(words(&WordType::Custom(PRAY)), vec!["pray"]);
+    }
+}
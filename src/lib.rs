@@ -11,29 +11,65 @@
 pub mod adventure_commands;
 /// Entity components built-in to bevy_adventure
 pub mod components;
+/// Conditions that decide when event handlers run
+pub mod conditions;
+/// Descriptions that change with the game's state
+pub mod description;
+/// Errors reported while the game runs
+pub mod error;
 /// The events built-in to bevy_adventure & their traits
 pub mod events;
+/// The game's flags, counters & variables
+pub mod flags;
+/// Headless harness for testing games with scripted input
+pub mod harness;
 /// Input & Output manager struct
 pub mod input_output_manager;
+/// Breaking the player's input into commands
+pub mod parser;
 /// Resources for the player
 pub mod player;
 /// The actual bevy_adventure plugin
 pub mod plugin;
+/// Typed room IDs & room lookup
+pub mod rooms;
+/// Saving & loading the game's state
+pub mod save;
 /// The systems built-in to bevy_adventure
 pub mod systems;
+/// Undoing the player's commands
+pub mod undo;
+/// Checking the game's world for mistakes at startup
+pub mod validation;
 
 // ========== Exports/Modules ==========
 
 /// Everything needed to use bevy_adventure. Import this when using the library.
 pub mod prelude {
     // Input manager
-    pub use crate::input_output_manager::{IOManager, WordType, KEYWORDS};
+    pub use crate::input_output_manager::{
+        AdventureIo, IOManager, TerminalIo, VerbId, Vocabulary, WordType, KEYWORDS,
+    };
     // Built in components
     pub use crate::components::*;
     // Trait modifying Bevy's Commands struct
     pub use crate::adventure_commands::{AdventureCommands, AdventureEntityCommands};
+    // Conditions for handlers
+    pub use crate::conditions::{Flag, NotFlag};
+    // Headless test harness
+    pub use crate::harness::{AdventureHarness, ScriptedIo, Transcript, TranscriptFile};
+    // The player's parsed command
+    pub use crate::parser::{InteractionContext, LastMentioned, PlayerCommand, Suggestions};
     // Player resources
-    pub use crate::player::{Checkpoints, Inventory};
+    pub use crate::player::{Checkpoints, Inventory, PlayTime};
+    // Game state
+    pub use crate::flags::{FlagChanged, FlagValue, Flags};
+    // Errors
+    pub use crate::error::AdventureError;
+    // Room IDs
+    pub use crate::rooms::{RoomChanged, RoomId};
+    // Save files
+    pub use crate::save::{SaveError, SaveSettings};
     // The plugin itself
-    pub use crate::plugin::AdventurePlugin;
+    pub use crate::plugin::{AdventureApp, AdventurePlugin};
 }
@@ -0,0 +1,670 @@
+use crate::components::{ActiveRoom, Room};
+use crate::input_output_manager::{Vocabulary, WordType};
+use bevy::prelude::{Entity, Resource, With, World};
+use std::ops::Range;
+
+/// Words that separate a command's direct object from its indirect object,
+/// like the "to" in "give bible to joseph"
+pub const PREPOSITIONS: &[&str] = &[
+    "to", "on", "onto", "in", "into", "inside", "with", "at", "from", "under", "over", "behind",
+];
+
+/// The command the player just typed, broken into its parts.
+///
+/// This is updated before each [OnInteract](crate::components::OnInteract) handler runs,
+/// so handlers can see everything the player mentioned.
+///
+/// If the command has an indirect object with a handler for the action, that handler runs
+/// (so in "give bible to joseph", Joseph's handler runs). Otherwise, the direct object's
+/// handler runs. With "with", the direct object always goes first ("open door with key").
+/// ```ignore
+/// // "use key on door" - this handler is on the door
+/// fn unlock_door(command: Res<PlayerCommand>, names: Query<&Name>, iomgr: Res<IOManager>) {
+///     let key = command.direct_object.and_then(|item| names.get(item).ok());
+///     if key.is_some_and(|name| name.0 == "key") {
+///         iomgr.println("The door unlocks.");
+///     }
+/// }
+/// ```
+#[derive(Resource, Debug, Clone, Default, PartialEq, Eq)]
+pub struct PlayerCommand {
+    /// Exactly what the player typed
+    pub input: String,
+    /// The action, like "give" in "give bible to joseph"
+    pub verb: Option<WordType>,
+    /// The first thing the command mentions, like "bible" in "give bible to joseph"
+    pub direct_object: Option<Entity>,
+    /// The word linking the two objects, like "to" in "give bible to joseph"
+    pub preposition: Option<&'static str>,
+    /// The second thing the command mentions, like "joseph" in "give bible to joseph"
+    pub indirect_object: Option<Entity>,
+    /// Words that weren't understood - they aren't a keyword, a stop word,
+    /// the preposition, or part of an object's name. These are lowercase.
+    pub unknown_words: Vec<String>,
+}
+impl PlayerCommand {
+    /// Break the player's input into a command.
+    ///
+    /// `candidates` are the entities the player could be talking about, with every name
+    /// they can be called by. Names can be several words long; the longest match wins.
+    ///
+    /// If there's only an object after the preposition (like "look at door"),
+    /// it becomes the direct object.
+    ///
+    /// Verbs are looked up in each of the `vocabularies`, in order. Stop words (see [is_stop_word])
+    /// are ignored, both in the input and in names.
+    ///
+    /// If the player's words match more than one entity, the first one is used;
+    /// see [PlayerCommand::parse_with] to choose between them.
+    pub fn parse(
+        input: &str,
+        candidates: &[(Entity, Vec<&'static str>)],
+        vocabularies: &[&Vocabulary],
+    ) -> Self {
+        Self::parse_with(input, candidates, vocabularies, |entities| entities[0])
+    }
+    /// The same as [PlayerCommand::parse], but when the player's words match more than one entity
+    /// (like two entities aliased "child"), `choose` picks which one they meant.
+    ///
+    /// Each choice is remembered for the rest of the command, so the player is never asked
+    /// about the same entities twice.
+    pub fn parse_with(
+        input: &str,
+        candidates: &[(Entity, Vec<&'static str>)],
+        vocabularies: &[&Vocabulary],
+        mut choose: impl FnMut(&[Entity]) -> Entity,
+    ) -> Self {
+        // Stop words (like "the" or "an") are dropped from the input and from names,
+        //  so they can't be mistaken for anything
+        let tokenize = |text: &str| -> Vec<String> {
+            text.split_whitespace()
+                .map(str::to_lowercase)
+                .filter(|word| !is_stop_word(vocabularies, word))
+                .collect()
+        };
+        let words = tokenize(input);
+        let candidates: Vec<(Entity, Vec<Vec<String>>)> = candidates
+            .iter()
+            .map(|(entity, names)| (*entity, names.iter().map(|name| tokenize(name)).collect()))
+            .collect();
+
+        // The first preposition that isn't part of a name splits the command in two
+        let split = words.iter().enumerate().find_map(|(index, word)| {
+            let preposition = PREPOSITIONS.iter().find(|prep| **prep == word.as_str())?;
+            let in_name = candidates
+                .iter()
+                .flat_map(|(_, names)| names)
+                .flat_map(|name| find_phrases(&words, name))
+                .any(|span| span.contains(&index));
+            if in_name {
+                None
+            } else {
+                Some((index, *preposition))
+            }
+        });
+
+        let (mut direct, mut indirect, preposition) = match split {
+            Some((index, preposition)) => (
+                longest_match(&words, 0..index, &candidates),
+                longest_match(&words, index + 1..words.len(), &candidates),
+                Some(preposition),
+            ),
+            None => (
+                longest_match(&words, 0..words.len(), &candidates),
+                None,
+                None,
+            ),
+        };
+        if direct.is_none() {
+            direct = indirect.take();
+        }
+
+        // Ask which entity the player meant, if there's more than one
+        let mut choices: Vec<(Vec<Entity>, Entity)> = Vec::new();
+        let mut resolve = |(entities, span): (Vec<Entity>, Range<usize>)| {
+            if entities.len() == 1 {
+                return (entities[0], span);
+            }
+            if let Some((_, chosen)) = choices.iter().find(|(options, _)| *options == entities) {
+                return (*chosen, span);
+            }
+            let chosen = choose(&entities);
+            choices.push((entities, chosen));
+            (chosen, span)
+        };
+        let direct = direct.map(&mut resolve);
+        let indirect = indirect.map(&mut resolve);
+
+        // The verb is the first keyword that isn't part of an object's name
+        let in_object = |index: &usize| {
+            direct
+                .iter()
+                .chain(indirect.iter())
+                .any(|(_, span)| span.contains(index))
+        };
+        let verb = words
+            .iter()
+            .enumerate()
+            .filter(|(index, _)| !in_object(index))
+            .find_map(|(_, word)| match Vocabulary::lookup(vocabularies, word) {
+                // Ignored words and non-keywords aren't actions
+                None | Some(WordType::Ignore) => None,
+                Some(word_type) => Some(word_type.clone()),
+            });
+
+        // Anything left over wasn't understood
+        let unknown_words = words
+            .iter()
+            .enumerate()
+            .filter(|(index, word)| {
+                !in_object(index)
+                    && split.map(|(split, _)| split) != Some(*index)
+                    && Vocabulary::lookup(vocabularies, word).is_none()
+            })
+            .map(|(_, word)| word.clone())
+            .collect();
+
+        Self {
+            input: input.to_owned(),
+            verb,
+            direct_object: direct.map(|(entity, _)| entity),
+            preposition,
+            indirect_object: indirect.map(|(entity, _)| entity),
+            unknown_words,
+        }
+    }
+}
+
+/// What to do when the player mentions something that isn't there, or a verb the game doesn't
+/// know, but it's close to something that is - like "kds" instead of "kids".
+///
+/// Suggestions are only made once nothing in the room could handle the command, so words a
+/// [WordType::Any] handler understands aren't mistaken for typos.
+#[derive(Resource, Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Suggestions {
+    /// Don't suggest anything
+    Off,
+    /// Print "Did you mean ...?"
+    Hint,
+    /// Print "Did you mean ...?", and let the player answer yes to run the corrected command
+    #[default]
+    Prompt,
+}
+
+/// A correction for a word the player probably mistyped
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Suggestion {
+    /// The word the player typed (lowercase)
+    pub word: String,
+    /// The word they probably meant
+    pub replacement: String,
+    /// If the word was meant to be a verb, rather than the name of something
+    pub is_verb: bool,
+}
+impl Suggestion {
+    /// Find a suggestion for the first unknown word in `command` that's close to a name or a verb.
+    ///
+    /// Names are only suggested if the command has no direct object, and verbs only if it has no verb.
+    /// A word from a name is only suggested if the corrected command then matches a whole name,
+    /// and the word the player typed is never suggested back to them.
+    pub fn find(
+        command: &PlayerCommand,
+        candidates: &[(Entity, Vec<&'static str>)],
+        vocabularies: &[&Vocabulary],
+    ) -> Option<Self> {
+        // Every single word of every name, since the player might only mistype part of one
+        let mut names: Vec<String> = Vec::new();
+        if command.direct_object.is_none() {
+            for name in candidates.iter().flat_map(|(_, names)| names) {
+                for word in name.split_whitespace().map(str::to_lowercase) {
+                    if !names.contains(&word) && !is_stop_word(vocabularies, &word) {
+                        names.push(word);
+                    }
+                }
+            }
+        }
+        let mut verbs: Vec<&str> = Vec::new();
+        if command.verb.is_none() {
+            for vocabulary in vocabularies {
+                for (word, meaning) in vocabulary.iter() {
+                    if *meaning != WordType::Ignore && !verbs.contains(&word) {
+                        verbs.push(word);
+                    }
+                }
+            }
+            verbs.sort_unstable();
+        }
+
+        command.unknown_words.iter().find_map(|word| {
+            // Part of a name is only worth suggesting if the corrected command names something
+            let name = closest(word, names.iter().map(String::as_str), |name| {
+                let corrected = Self {
+                    word: word.clone(),
+                    replacement: name.to_owned(),
+                    is_verb: false,
+                }
+                .apply(&command.input);
+                PlayerCommand::parse(&corrected, candidates, vocabularies)
+                    .direct_object
+                    .is_some()
+            });
+            let verb = closest(word, verbs.iter().copied(), |_| true);
+
+            // Names win ties, since they're what the player is usually looking for
+            let (replacement, is_verb) = match (name, verb) {
+                (Some(name), Some(verb)) if verb.0 < name.0 => (verb.1, true),
+                (Some(name), _) => (name.1, false),
+                (None, Some(verb)) => (verb.1, true),
+                (None, None) => return None,
+            };
+            Some(Self {
+                word: word.clone(),
+                replacement: replacement.to_owned(),
+                is_verb,
+            })
+        })
+    }
+    /// The player's input, with the mistyped word replaced
+    pub fn apply(&self, input: &str) -> String {
+        input
+            .split_whitespace()
+            .map(|word| {
+                if word.to_lowercase() == self.word {
+                    self.replacement.as_str()
+                } else {
+                    word
+                }
+            })
+            .collect::<Vec<_>>()
+            .join(" ")
+    }
+}
+
+// The option closest to `word` that `accept`s, and how far it is, if it's close enough to be
+// a typo. `word` itself is never suggested. Ties go to the first option.
+fn closest<'a>(
+    word: &str,
+    options: impl Iterator<Item = &'a str>,
+    mut accept: impl FnMut(&str) -> bool,
+) -> Option<(usize, &'a str)> {
+    options
+        .filter(|option| *option != word)
+        .map(|option| (edit_distance(word, option), option))
+        .filter(|(distance, option)| *distance <= max_typos(word) && accept(option))
+        .min_by_key(|(distance, _)| *distance)
+}
+
+// How many typos a word can have and still get a suggestion
+fn max_typos(word: &str) -> usize {
+    match word.chars().count() {
+        0..=2 => 0,
+        3..=5 => 1,
+        _ => 2,
+    }
+}
+
+/// How many single-character edits (insertions, deletions, substitutions, or swaps of two
+/// neighbouring characters) it takes to turn `a` into `b`
+pub fn edit_distance(a: &str, b: &str) -> usize {
+    let a: Vec<char> = a.chars().collect();
+    let b: Vec<char> = b.chars().collect();
+
+    // distances[i][j] = distance between a[..i] and b[..j]
+    let mut distances = vec![vec![0usize; b.len() + 1]; a.len() + 1];
+    for (i, row) in distances.iter_mut().enumerate() {
+        row[0] = i;
+    }
+    for (j, distance) in distances[0].iter_mut().enumerate() {
+        *distance = j;
+    }
+    for i in 1..=a.len() {
+        for j in 1..=b.len() {
+            let cost = usize::from(a[i - 1] != b[j - 1]);
+            let mut distance = (distances[i - 1][j] + 1)
+                .min(distances[i][j - 1] + 1)
+                .min(distances[i - 1][j - 1] + cost);
+            if i > 1 && j > 1 && a[i - 1] == b[j - 2] && a[i - 2] == b[j - 1] {
+                distance = distance.min(distances[i - 2][j - 2] + 1);
+            }
+            distances[i][j] = distance;
+        }
+    }
+    distances[a.len()][b.len()]
+}
+
+/// If `word` is a stop word - a word like "the" or "an" that doesn't mean anything to the parser.
+///
+/// Stop words are words the vocabularies map to [WordType::Ignore]. Prepositions are never
+/// stop words, since they're needed to split two-object commands.
+pub fn is_stop_word(vocabularies: &[&Vocabulary], word: &str) -> bool {
+    !PREPOSITIONS.contains(&word.to_lowercase().as_str())
+        && Vocabulary::lookup(vocabularies, word) == Some(&WordType::Ignore)
+}
+
+// Find the candidates with the longest name in `within`. If several entities match the
+// same words (like two entities aliased "child"), they're all returned.
+fn longest_match(
+    words: &[String],
+    within: Range<usize>,
+    candidates: &[(Entity, Vec<Vec<String>>)],
+) -> Option<(Vec<Entity>, Range<usize>)> {
+    let offset = within.start;
+    let mut best: Option<(Vec<Entity>, Range<usize>)> = None;
+
+    for (entity, names) in candidates {
+        for name in names {
+            if let Some(span) = find_phrases(&words[within.clone()], name).next() {
+                let span = span.start + offset..span.end + offset;
+                match &mut best {
+                    Some((entities, best)) if span == *best => {
+                        if !entities.contains(entity) {
+                            entities.push(*entity);
+                        }
+                    }
+                    Some((_, best)) if span.len() <= best.len() => {}
+                    _ => best = Some((vec![*entity], span)),
+                }
+            }
+        }
+    }
+    best
+}
+
+// Every place a (tokenized) name appears in the player's input
+fn find_phrases<'a>(
+    words: &'a [String],
+    phrase: &'a [String],
+) -> impl Iterator<Item = Range<usize>> + 'a {
+    let len = phrase.len();
+    // (An empty name never matches, since every window has at least one word)
+    words
+        .windows(len.max(1))
+        .enumerate()
+        .filter(move |(_, window)| *window == phrase)
+        .map(move |(start, _)| start..start + len)
+}
+
+/// The entities the player mentioned in their last command that mentioned anything.
+/// Pronouns (see [Pronouns](crate::components::Pronouns)) refer to these.
+#[derive(Resource, Debug, Clone, Default, PartialEq, Eq)]
+pub struct LastMentioned(pub Vec<Entity>);
+impl LastMentioned {
+    /// Remember the objects in `command`. Commands that don't mention anything are skipped,
+    /// so "look", then "take it" still works.
+    pub fn update(&mut self, command: &PlayerCommand) {
+        let mentioned: Vec<Entity> = [command.direct_object, command.indirect_object]
+            .into_iter()
+            .flatten()
+            .collect();
+        if !mentioned.is_empty() {
+            self.0 = mentioned;
+        }
+    }
+}
+
+/// Everything an [OnInteract](crate::components::OnInteract) handler might want to know about
+/// the interaction that triggered it.
+///
+/// Handlers can take this as their first parameter, with Bevy's `In`. This lets one handler
+/// deal with several verbs:
+/// ```ignore
+/// fn chest(In(context): In<InteractionContext>, iomgr: Res<IOManager>) {
+///     match context.word_type {
+///         Some(WordType::Open) => iomgr.println("The chest creaks open."),
+///         Some(WordType::Break) => iomgr.println("The chest is too sturdy to break."),
+///         _ => iomgr.println(format!("You can't {} the chest.", context.tokens[0])),
+///     }
+/// }
+///
+/// commands.spawn(Name("chest")).on_interact(WordType::Any, chest);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct InteractionContext {
+    /// Exactly what the player typed
+    pub input: String,
+    /// The player's input, split into lowercase words
+    pub tokens: Vec<String>,
+    /// The action the player used, if one was recognized
+    pub word_type: Option<WordType>,
+    /// The entity whose handler is running
+    pub target: Entity,
+    /// The other object the player mentioned, if there was one.
+    /// For "use key on door", this is the key if the door's handler is running, and vice-versa.
+    pub secondary: Option<Entity>,
+}
+impl InteractionContext {
+    /// The context for `target`'s handler running in response to `command`
+    pub fn new(command: &PlayerCommand, target: Entity) -> Self {
+        let secondary = [command.direct_object, command.indirect_object]
+            .into_iter()
+            .flatten()
+            .find(|object| *object != target);
+
+        Self {
+            input: command.input.clone(),
+            tokens: command
+                .input
+                .split_whitespace()
+                .map(str::to_lowercase)
+                .collect(),
+            word_type: command.verb.clone(),
+            target,
+            secondary,
+        }
+    }
+    /// The context of the player's last command, targeting its direct object
+    /// (or the active room, if it didn't have one)
+    pub fn from_world(world: &mut World) -> Self {
+        let command = world.resource::<PlayerCommand>().clone();
+        let target = match command.direct_object {
+            Some(target) => target,
+            None => world
+                .query_filtered::<Entity, (With<Room>, With<ActiveRoom>)>()
+                .single(world),
+        };
+        Self::new(&command, target)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entity(index: u32) -> Entity {
+        Entity::from_raw(index)
+    }
+
+    #[test]
+    fn longest_name_wins() {
+        let vocabulary = Vocabulary::built_in();
+        let candidates = [(entity(0), vec!["door"]), (entity(1), vec!["front door"])];
+
+        let command = PlayerCommand::parse("open front door", &candidates, &[&vocabulary]);
+        assert_eq!(command.verb, Some(WordType::Open));
+        assert_eq!(command.direct_object, Some(entity(1)));
+        assert!(command.unknown_words.is_empty());
+
+        let command = PlayerCommand::parse("open door", &candidates, &[&vocabulary]);
+        assert_eq!(command.direct_object, Some(entity(0)));
+    }
+
+    #[test]
+    fn aliases_match_like_names() {
+        let vocabulary = Vocabulary::built_in();
+        let candidates = [(entity(0), vec!["kids", "the children"])];
+
+        let command = PlayerCommand::parse("take children", &candidates, &[&vocabulary]);
+        assert_eq!(command.direct_object, Some(entity(0)));
+        let command = PlayerCommand::parse("take the children", &candidates, &[&vocabulary]);
+        assert_eq!(command.direct_object, Some(entity(0)));
+    }
+
+    #[test]
+    fn names_ignore_case() {
+        let vocabulary = Vocabulary::built_in();
+        let candidates = [(entity(0), vec!["kids"]), (entity(1), vec!["Front Door"])];
+
+        let command = PlayerCommand::parse("Take Kids", &candidates, &[&vocabulary]);
+        assert_eq!(command.verb, Some(WordType::Take));
+        assert_eq!(command.direct_object, Some(entity(0)));
+
+        let command = PlayerCommand::parse("OPEN front DOOR", &candidates, &[&vocabulary]);
+        assert_eq!(command.verb, Some(WordType::Open));
+        assert_eq!(command.direct_object, Some(entity(1)));
+    }
+
+    #[test]
+    fn prepositions_split_two_objects() {
+        let vocabulary = Vocabulary::built_in();
+        let candidates = [(entity(0), vec!["bible"]), (entity(1), vec!["joseph"])];
+
+        let command = PlayerCommand::parse("give bible to joseph", &candidates, &[&vocabulary]);
+        assert_eq!(command.verb, Some(WordType::Give));
+        assert_eq!(command.direct_object, Some(entity(0)));
+        assert_eq!(command.preposition, Some("to"));
+        assert_eq!(command.indirect_object, Some(entity(1)));
+        assert!(command.unknown_words.is_empty());
+    }
+
+    #[test]
+    fn prepositions_in_names_dont_split() {
+        let vocabulary = Vocabulary::built_in();
+        let candidates = [(entity(0), vec!["key"]), (entity(1), vec!["man in black"])];
+
+        let command = PlayerCommand::parse("give key to man in black", &candidates, &[&vocabulary]);
+        assert_eq!(command.direct_object, Some(entity(0)));
+        assert_eq!(command.preposition, Some("to"));
+        assert_eq!(command.indirect_object, Some(entity(1)));
+
+        let command = PlayerCommand::parse("talk to man in black", &candidates, &[&vocabulary]);
+        assert_eq!(command.direct_object, Some(entity(1)));
+        assert_eq!(command.indirect_object, None);
+    }
+
+    #[test]
+    fn lone_indirect_objects_become_direct() {
+        let vocabulary = Vocabulary::built_in();
+        let candidates = [(entity(0), vec!["door"])];
+
+        let command = PlayerCommand::parse("look at the door", &candidates, &[&vocabulary]);
+        assert_eq!(command.verb, Some(WordType::Look));
+        assert_eq!(command.direct_object, Some(entity(0)));
+        assert_eq!(command.preposition, Some("at"));
+        assert_eq!(command.indirect_object, None);
+    }
+
+    #[test]
+    fn with_keeps_both_objects() {
+        let vocabulary = Vocabulary::built_in();
+        let candidates = [(entity(0), vec!["door"]), (entity(1), vec!["key"])];
+
+        let command = PlayerCommand::parse("open door with key", &candidates, &[&vocabulary]);
+        assert_eq!(command.direct_object, Some(entity(0)));
+        assert_eq!(command.preposition, Some("with"));
+        assert_eq!(command.indirect_object, Some(entity(1)));
+    }
+
+    #[test]
+    fn stop_words() {
+        let mut vocabulary = Vocabulary::built_in();
+        vocabulary.add_stop_word("yonder");
+        let vocabularies = [&vocabulary];
+
+        assert!(is_stop_word(&vocabularies, "the"));
+        assert!(is_stop_word(&vocabularies, "An"));
+        assert!(is_stop_word(&vocabularies, "yonder"));
+        // Only articles are built in
+        assert!(!is_stop_word(&vocabularies, "my"));
+        assert!(!is_stop_word(&vocabularies, "door"));
+        assert!(!is_stop_word(&vocabularies, "take"));
+        // Prepositions are needed to split commands, even if they're also ignored words
+        vocabulary.add_stop_word("with");
+        assert!(!is_stop_word(&[&vocabulary], "with"));
+    }
+
+    #[test]
+    fn stop_words_are_skipped() {
+        let mut vocabulary = Vocabulary::built_in();
+        vocabulary.add_stop_word("please");
+        let candidates = [(entity(0), vec!["the front door"])];
+
+        let command = PlayerCommand::parse("please open a front door", &candidates, &[&vocabulary]);
+        assert_eq!(command.verb, Some(WordType::Open));
+        assert_eq!(command.direct_object, Some(entity(0)));
+        assert!(command.unknown_words.is_empty());
+
+        // Names match with or without their stop words
+        let command = PlayerCommand::parse("open front door", &candidates, &[&vocabulary]);
+        assert_eq!(command.direct_object, Some(entity(0)));
+    }
+
+    #[test]
+    fn edit_distances() {
+        assert_eq!(edit_distance("kids", "kids"), 0);
+        assert_eq!(edit_distance("kds", "kids"), 1);
+        assert_eq!(edit_distance("kidz", "kids"), 1);
+        // Swapping two neighbouring characters is one typo
+        assert_eq!(edit_distance("kdis", "kids"), 1);
+        assert_eq!(edit_distance("", "abc"), 3);
+        assert_eq!(edit_distance("kitten", "sitting"), 3);
+    }
+
+    #[test]
+    fn suggest_part_of_a_name() {
+        let vocabulary = Vocabulary::built_in();
+        let candidates = [(entity(0), vec!["front door"])];
+        let command = PlayerCommand::parse("open frnt door", &candidates, &[&vocabulary]);
+
+        let suggestion = Suggestion::find(&command, &candidates, &[&vocabulary]);
+        assert_eq!(
+            suggestion,
+            Some(Suggestion {
+                word: "frnt".to_owned(),
+                replacement: "front".to_owned(),
+                is_verb: false,
+            })
+        );
+    }
+
+    #[test]
+    fn dont_suggest_incomplete_names() {
+        let vocabulary = Vocabulary::built_in();
+        let candidates = [(entity(0), vec!["front door"])];
+
+        // "open front" still doesn't name anything
+        let command = PlayerCommand::parse("open frnt", &candidates, &[&vocabulary]);
+        assert_eq!(
+            Suggestion::find(&command, &candidates, &[&vocabulary]),
+            None
+        );
+        // And the player's own word isn't suggested back to them
+        let command = PlayerCommand::parse("open front", &candidates, &[&vocabulary]);
+        assert_eq!(command.unknown_words, vec!["front"]);
+        assert_eq!(
+            Suggestion::find(&command, &candidates, &[&vocabulary]),
+            None
+        );
+    }
+
+    #[test]
+    fn suggest_verbs() {
+        let vocabulary = Vocabulary::built_in();
+        let candidates = [(entity(0), vec!["door"])];
+        let command = PlayerCommand::parse("opn door", &candidates, &[&vocabulary]);
+
+        let suggestion = Suggestion::find(&command, &candidates, &[&vocabulary]).unwrap();
+        assert_eq!(suggestion.replacement, "open");
+        assert!(suggestion.is_verb);
+    }
+
+    #[test]
+    fn apply_suggestions() {
+        let suggestion = Suggestion {
+            word: "frnt".to_owned(),
+            replacement: "front".to_owned(),
+            is_verb: false,
+        };
+        // Only the mistyped word changes, whatever its case
+        assert_eq!(suggestion.apply("Open FRNT  door"), "Open front door");
+    }
+}
@@ -1,9 +1,98 @@
-use bevy::prelude::{Entity, Resource};
+use crate::components::{ActiveRoom, Name};
+use crate::input_output_manager::IOManager;
+use crate::systems::list_names;
+use bevy::hierarchy::{BuildWorldChildren, Parent};
+use bevy::prelude::{Entity, Resource, With, World};
+use std::time::Duration;
 
 pub trait InventoryItem: Sync + Send {}
 
+/// The entities the player is carrying. These can be used from any room.
+///
+/// Taking a [Portable](crate::components::Portable) entity adds it here, and removes it from its room.
 #[derive(Resource)]
 pub struct Inventory(pub Vec<Entity>);
+impl Inventory {
+    /// If the player is carrying `entity`
+    pub fn contains(&self, entity: Entity) -> bool {
+        self.0.contains(&entity)
+    }
+}
 
+/// The names of every boolean [Flag](crate::flags::Flags) that's `true`.
+///
+/// This is kept for games written before [Flags](crate::flags::Flags) existed. Pushing a checkpoint
+/// sets its flag, removing one clears it, and changes to the flags show up here; the two are
+/// synced every frame.
 #[derive(Resource)]
 pub struct Checkpoints(pub Vec<&'static str>);
+
+/// How long the player has been playing the game
+#[derive(Resource, Default)]
+pub struct PlayTime(pub Duration);
+
+/// Move an entity out of its room and into the [Inventory]. See [AdventureCommands::take_item](crate::adventure_commands::AdventureCommands::take_item).
+pub(crate) fn take_item(world: &mut World, entity: Entity) {
+    let name = item_name(world, entity);
+    if world.resource::<Inventory>().contains(entity) {
+        world
+            .resource::<IOManager>()
+            .println(format!("You already have the {}.", name));
+        return;
+    }
+
+    // Detach it from the room, so it goes wherever the player goes
+    if let Some(parent) = world.get::<Parent>(entity).map(Parent::get) {
+        world.entity_mut(parent).remove_children(&[entity]);
+    }
+    world.resource_mut::<Inventory>().0.push(entity);
+    world
+        .resource::<IOManager>()
+        .println(format!("You take the {}.", name));
+}
+
+/// Move an entity out of the [Inventory] and into the active room. See [AdventureCommands::drop_item](crate::adventure_commands::AdventureCommands::drop_item).
+pub(crate) fn drop_item(world: &mut World, entity: Entity) {
+    let name = item_name(world, entity);
+    if !world.resource::<Inventory>().contains(entity) {
+        world
+            .resource::<IOManager>()
+            .println(format!("You aren't carrying the {}.", name));
+        return;
+    }
+
+    world
+        .resource_mut::<Inventory>()
+        .0
+        .retain(|item| *item != entity);
+    let room = world
+        .query_filtered::<Entity, With<ActiveRoom>>()
+        .single(world);
+    world.entity_mut(room).push_children(&[entity]);
+    world
+        .resource::<IOManager>()
+        .println(format!("You drop the {}.", name));
+}
+
+/// Tell the player what's in their [Inventory]. This is what the `inventory` command does.
+pub(crate) fn list_inventory(world: &mut World) {
+    let names: Vec<&str> = world
+        .resource::<Inventory>()
+        .0
+        .iter()
+        .filter_map(|entity| world.get::<Name>(*entity))
+        .map(|name| name.0)
+        .collect();
+
+    let iomgr = world.resource::<IOManager>();
+    if names.is_empty() {
+        iomgr.println("You aren't carrying anything.");
+    } else {
+        iomgr.println(format!("You're carrying: {}.", list_names(&names, "and")));
+    }
+}
+
+// What to call an item when talking about it
+fn item_name(world: &World, entity: Entity) -> &'static str {
+    world.get::<Name>(entity).map_or("item", |name| name.0)
+}
@@ -1,8 +1,17 @@
-use crate::input_output_manager::IOManager;
-use crate::player::{Checkpoints, Inventory};
+use crate::error::{AdventureError, ErrorHandler};
+use crate::events::{EventHandler, IntoEventHandler};
+use crate::flags::{FlagChanged, Flags, SyncedCheckpoints};
+use crate::input_output_manager::{IOManager, VerbId, Vocabulary};
+use crate::parser::{LastMentioned, PlayerCommand, Suggestions};
+use crate::player::{Checkpoints, Inventory, PlayTime};
+use crate::rooms::{RoomChanged, RoomIndex};
+use crate::save::SaveSettings;
 use crate::systems;
+use crate::undo::UndoHistory;
+use crate::validation;
 use bevy::app::{App, Plugin, PluginGroup, PluginGroupBuilder};
-use bevy::prelude::MinimalPlugins;
+use bevy::prelude::{MinimalPlugins, Resource, StartupStage};
+use std::sync::{Arc, Mutex};
 
 /// Plugins needed by bevy_adventure.
 pub struct AdventureDefaultPlugins;
@@ -21,7 +30,45 @@ impl PluginGroup for AdventureDefaultPlugins {
 }
 
 /// The bevy_adventure plugin itself. This adds [AdventureDefaultPlugins] and [MinimalPlugins] to the app.
-pub struct AdventurePlugin;
+///
+/// Use `AdventurePlugin::new(<game name>)` for the default settings. The game's name is where
+/// its saves are kept, so it should be unique to your game - the crate's name works well:
+/// ```ignore
+/// app.add_plugin(AdventurePlugin::new(env!("CARGO_PKG_NAME")));
+/// ```
+///
+/// `AdventurePlugin` used to be a unit struct, so `app.add_plugin(AdventurePlugin)` no longer
+/// compiles. `AdventurePlugin::default()` is a drop-in replacement, but it keeps saves under
+/// `bevy_adventure`, which every game using it shares, so prefer giving the game's own name.
+pub struct AdventurePlugin {
+    /// The game's name, which decides where saves are kept (see [SaveSettings::new])
+    pub game_name: &'static str,
+    /// How many commands the `undo` command can go back (0 disables undo)
+    pub undo_depth: usize,
+    /// Check the world for mistakes at startup (see [validate_world](crate::validation::validate_world)).
+    /// Problems are printed to stderr, and errors stop the game.
+    ///
+    /// This is on by default in debug builds, and off in release builds.
+    pub validate_world: bool,
+    /// What to do when the player probably made a typo (see [Suggestions])
+    pub suggestions: Suggestions,
+}
+impl AdventurePlugin {
+    /// The default settings, for the game called `game_name`
+    pub fn new(game_name: &'static str) -> Self {
+        Self {
+            game_name,
+            undo_depth: 10,
+            validate_world: cfg!(debug_assertions),
+            suggestions: Suggestions::default(),
+        }
+    }
+}
+impl Default for AdventurePlugin {
+    fn default() -> Self {
+        Self::new("bevy_adventure")
+    }
+}
 impl Plugin for AdventurePlugin {
     fn build(&self, app: &mut App) {
         let new_room_state = systems::NewRoomState::new(&mut app.world);
@@ -33,14 +80,107 @@ impl Plugin for AdventurePlugin {
             .add_plugins(AdventureDefaultPlugins)
             // IOManager Resource
             .insert_resource(IOManager::new())
+            // Words the player can use
+            .init_resource::<Vocabulary>()
             // Inventory Resrouce
             .insert_resource(Inventory(Vec::new()))
             // Checkpoint resource
             .insert_resource(Checkpoints(Vec::new()))
+            // Game flags, which Checkpoints is synced with
+            .init_resource::<Flags>()
+            .init_resource::<SyncedCheckpoints>()
+            .add_event::<FlagChanged>()
+            // Play time resource
+            .init_resource::<PlayTime>()
+            // Save slot location
+            .insert_resource(SaveSettings::new(self.game_name))
+            // Undo snapshots
+            .insert_resource(UndoHistory::new(self.undo_depth))
+            // Systems that build the game's rooms
+            .init_resource::<WorldBuilders>()
+            // Room lookup
+            .init_resource::<RoomIndex>()
+            .add_event::<RoomChanged>()
+            // The command the player just typed
+            .init_resource::<PlayerCommand>()
+            .init_resource::<LastMentioned>()
+            .insert_resource(self.suggestions)
+            // Errors that don't crash the game
+            .add_event::<AdventureError>()
+            .init_resource::<ErrorHandler>()
             // Cached SystemStates
             .insert_resource(new_room_state);
 
         // Add systems
         systems::append_systems(app);
+        // Check the world for mistakes once it's built
+        if self.validate_world {
+            app.add_startup_system_to_stage(
+                StartupStage::PostStartup,
+                validation::validation_system,
+            );
+        }
+    }
+}
+
+// ========== WORLD BUILDERS ==========
+
+/// The systems that build the game's world, registered with [AdventureApp::add_world_builder].
+///
+/// These are stored so the world can be rebuilt when the game restarts.
+#[derive(Resource, Default)]
+pub struct WorldBuilders(pub(crate) Vec<Arc<Mutex<dyn EventHandler>>>);
+
+/// Add methods to Bevy's [App] struct
+pub trait AdventureApp {
+    /// Add a system that spawns the game's rooms & entities.
+    ///
+    /// This works like a startup system, except bevy_adventure remembers it, so it can
+    /// be run again to rebuild the world when the game restarts (see
+    /// [AdventureCommands::restart_game](crate::adventure_commands::AdventureCommands::restart_game)).
+    /// Any startup system that spawns rooms should be added with this instead of `add_startup_system`.
+    fn add_world_builder<Params>(&mut self, builder: impl IntoEventHandler<Params>) -> &mut Self;
+    /// Set a system to run whenever an [AdventureError] is reported, such as when
+    /// [try_set_room](crate::adventure_commands::AdventureCommands::try_set_room) can't find a room.
+    ///
+    /// The error itself can be read with an `EventReader<AdventureError>`:
+    /// ```ignore
+    /// app.set_error_handler(|mut errors: EventReader<AdventureError>, iomgr: Res<IOManager>| {
+    ///     for err in errors.iter() {
+    ///         iomgr.println(format!("Something went wrong: {}", err));
+    ///     }
+    /// });
+    /// ```
+    fn set_error_handler<Params>(&mut self, handler: impl IntoEventHandler<Params>) -> &mut Self;
+    /// Declare a custom verb, and the words the player can use for it.
+    /// This adds it to the [Vocabulary] resource; see [Vocabulary::add_verb].
+    ///
+    /// Custom verbs are bound with `on_interact(WordType::Custom(<VerbId>), ...)`,
+    /// just like the built-in ones, and are listed by the `help` command.
+    fn add_verb(&mut self, verb: VerbId, words: &[&str]) -> &mut Self;
+}
+impl AdventureApp for App {
+    fn add_world_builder<Params>(&mut self, builder: impl IntoEventHandler<Params>) -> &mut Self {
+        self.world
+            .get_resource_or_insert_with(WorldBuilders::default)
+            .0
+            .push(builder.into_event());
+        self
+    }
+
+    fn set_error_handler<Params>(&mut self, handler: impl IntoEventHandler<Params>) -> &mut Self {
+        let handler = handler.into_event();
+        handler.lock().unwrap().init(&mut self.world);
+        self.world
+            .get_resource_or_insert_with(ErrorHandler::default)
+            .0 = Some(handler);
+        self
+    }
+
+    fn add_verb(&mut self, verb: VerbId, words: &[&str]) -> &mut Self {
+        self.world
+            .get_resource_or_insert_with(Vocabulary::default)
+            .add_verb(verb, words);
+        self
     }
 }
@@ -0,0 +1,85 @@
+use crate::components::Room;
+use bevy::prelude::{Entity, Resource, World};
+use bevy::utils::HashMap;
+use std::fmt::{self, Display};
+
+/// Identifies a room - used by [set_room](crate::adventure_commands::AdventureCommands::set_room),
+/// [Exits](crate::components::Exits), and [RoomChanged].
+///
+/// A RoomId is just the room's name. Declare each room's ID once, as a constant, and use that
+/// constant both to name the room and everywhere else it's mentioned:
+/// ```ignore
+/// const KITCHEN: RoomId = RoomId::new("Kitchen");
+///
+/// commands.spawn(Room { name: KITCHEN.name(), description: None });
+/// commands.set_room(KITCHEN);
+/// ```
+///
+/// Then the room's name can't drift away from its ID, and misspelling the constant is a compile
+/// error. The compiler can't check that an ID names a room that exists, though: exits to missing
+/// rooms are reported by [validation](crate::validation) at startup, and `set_room` panics if its
+/// room is missing (`try_set_room` reports an [AdventureError](crate::error::AdventureError) instead).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct RoomId(&'static str);
+impl RoomId {
+    /// The ID of the room called `name`
+    pub const fn new(name: &'static str) -> Self {
+        Self(name)
+    }
+    /// The name of the room this ID points to
+    pub const fn name(self) -> &'static str {
+        self.0
+    }
+}
+impl Display for RoomId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+/// Sent whenever the active room changes
+pub struct RoomChanged {
+    /// The room the player left
+    pub from: RoomId,
+    /// The room the player entered
+    pub to: RoomId,
+}
+
+/// Maps each [RoomId] to its room entity, so changing rooms doesn't have to search every room.
+///
+/// The index is built at startup, and rebuilt automatically whenever a lookup finds it out of date.
+#[derive(Resource, Default)]
+pub struct RoomIndex(HashMap<RoomId, Entity>);
+impl RoomIndex {
+    /// Get the entity of a room. This may be out of date if rooms were spawned or
+    /// despawned since the index was last built; use [RoomIndex::find] to be sure.
+    pub fn get(&self, room: RoomId) -> Option<Entity> {
+        self.0.get(&room).copied()
+    }
+    /// Every room in the index
+    pub fn iter(&self) -> impl Iterator<Item = (RoomId, Entity)> + '_ {
+        self.0.iter().map(|(id, entity)| (*id, *entity))
+    }
+    /// Re-index every room in the world
+    pub fn rebuild(world: &mut World) {
+        let rooms = world
+            .query::<(Entity, &Room)>()
+            .iter(world)
+            .map(|(entity, room)| (room.id(), entity))
+            .collect();
+        world.resource_mut::<RoomIndex>().0 = rooms;
+    }
+    /// Find a room's entity, rebuilding the index if it's out of date
+    pub fn find(world: &mut World, room: RoomId) -> Option<Entity> {
+        // Make sure the indexed entity still exists, and is still that room
+        let indexed = world.resource::<RoomIndex>().get(room);
+        if let Some(entity) = indexed {
+            if world.get::<Room>(entity).map(Room::id) == Some(room) {
+                return Some(entity);
+            }
+        }
+
+        Self::rebuild(world);
+        world.resource::<RoomIndex>().get(room)
+    }
+}
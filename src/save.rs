@@ -0,0 +1,630 @@
+use crate::components::{ActiveRoom, Health, Name, Portable, Room};
+use crate::flags::{self, Flags};
+use crate::input_output_manager::IOManager;
+use crate::player::{Inventory, PlayTime};
+use crate::plugin::WorldBuilders;
+use crate::systems::{self, move_active_room};
+use crate::undo::UndoHistory;
+use bevy::hierarchy::{BuildWorldChildren, DespawnRecursiveExt, Parent};
+use bevy::prelude::{Entity, Resource, With, World};
+use bevy::utils::HashMap;
+use serde::{Deserialize, Serialize};
+use std::cmp::Reverse;
+use std::fmt::{self, Display};
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+use std::{fs, io};
+
+/// The version of the save file format. Bump this whenever [SaveFile] changes.
+pub const SAVE_VERSION: u32 = 1;
+
+/// The file extension for save files
+pub const SAVE_EXTENSION: &str = "sav";
+
+// ========== SAVE FILE ==========
+
+/// Identifies an entity across runs of the game, since [Entity] IDs can change between runs.
+///
+/// This is the entity's [Name] (or [Room] name), prefixed by the names of its named ancestors.
+/// So, the `bed` in the room `Remove One` is `["Remove One", "bed"]`.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct EntityPath(pub Vec<String>);
+
+/// Everything bevy_adventure saves about a game.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SaveFile {
+    /// The save format version (see [SAVE_VERSION])
+    pub version: u32,
+    /// The player-visible name of the save
+    pub label: String,
+    /// When the game was saved, in seconds since the Unix epoch
+    pub saved_at: u64,
+    /// How long the player had been playing, in seconds
+    pub play_time: u64,
+    /// Name of the active room
+    pub room: String,
+    /// If AutoPrompt was on
+    pub autoprompt: bool,
+    /// The game's [Flags], which include the
+    /// [Checkpoints](crate::player::Checkpoints) the player has reached
+    pub flags: Flags,
+    /// The entities in the player's [Inventory]
+    pub inventory: Vec<EntityPath>,
+    /// Where every [Portable] entity is: its path, and its parent's path
+    /// (`None` if it has no parent, like when it's in the inventory)
+    pub items: Vec<(EntityPath, Option<EntityPath>)>,
+    /// The [Health] of every living, named entity
+    pub health: Vec<(EntityPath, i32)>,
+}
+
+impl SaveFile {
+    /// A one-line summary of the save, for listing save slots
+    pub fn summary(&self) -> String {
+        format!(
+            "{} - {} (played {}, saved {})",
+            self.label,
+            self.room,
+            format_play_time(self.play_time),
+            format_timestamp(self.saved_at)
+        )
+    }
+}
+
+/// Where the `save` and `load` commands keep save slots.
+///
+/// [AdventurePlugin](crate::plugin::AdventurePlugin) sets this up from the game's name;
+/// insert your own after adding the plugin to keep saves somewhere else.
+#[derive(Resource)]
+pub struct SaveSettings {
+    /// The directory save slots are stored in
+    pub directory: PathBuf,
+}
+impl SaveSettings {
+    /// Store saves in `<user data directory>/<game_name>/saves`
+    /// (for example, `~/.local/share/<game_name>/saves` on Linux).
+    ///
+    /// If the data directory can't be found, saves go in `./saves` instead.
+    pub fn new(game_name: &str) -> Self {
+        let directory = match dirs::data_dir() {
+            Some(data_dir) => data_dir.join(game_name).join("saves"),
+            None => PathBuf::from("saves"),
+        };
+        Self { directory }
+    }
+    /// Store saves in a specific directory
+    pub fn in_directory(directory: impl Into<PathBuf>) -> Self {
+        Self {
+            directory: directory.into(),
+        }
+    }
+    /// The path of the save file for a slot
+    pub fn slot_path(&self, slot: &str) -> PathBuf {
+        // Not `with_extension`, which would replace anything after a dot in the slot's ID
+        self.directory.join(format!("{}.{}", slot, SAVE_EXTENSION))
+    }
+}
+
+/// A save file on disk
+#[derive(Debug, Clone)]
+pub struct SaveSlot {
+    /// The slot's ID (its file name, without the extension)
+    pub id: String,
+    /// The save stored in the slot
+    pub save: SaveFile,
+}
+
+// ========== ERRORS ==========
+
+/// Errors from saving or loading the game
+#[derive(Debug)]
+pub enum SaveError {
+    /// The save file couldn't be read or written
+    Io(io::Error),
+    /// The save file couldn't be parsed
+    Format(ron::error::SpannedError),
+    /// The game state couldn't be serialized
+    Serialize(ron::Error),
+    /// The save file was made by a different version of bevy_adventure
+    Version { found: u32, expected: u32 },
+    /// The save file's active room doesn't exist in this game
+    MissingRoom(String),
+    /// An entity in the save file's inventory, items or health doesn't exist in this game
+    MissingEntity(EntityPath),
+}
+impl Display for SaveError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Io(err) => write!(f, "{}", err),
+            Self::Format(err) => write!(f, "the save file is corrupted ({})", err),
+            Self::Serialize(err) => write!(f, "the game couldn't be serialized ({})", err),
+            Self::Version { found, expected } => write!(
+                f,
+                "the save file is version {}, but this game uses version {}",
+                found, expected
+            ),
+            Self::MissingRoom(room) => write!(f, "the room `{}` doesn't exist", room),
+            Self::MissingEntity(path) => write!(f, "`{}` doesn't exist", path.0.join("/")),
+        }
+    }
+}
+impl std::error::Error for SaveError {}
+impl From<io::Error> for SaveError {
+    fn from(err: io::Error) -> Self {
+        Self::Io(err)
+    }
+}
+
+// ========== SAVE ==========
+
+/// Capture the current game state as a [SaveFile], labelled `label`
+pub fn snapshot(world: &mut World, label: impl Into<String>) -> SaveFile {
+    let room = world
+        .query_filtered::<&Room, With<ActiveRoom>>()
+        .single(world)
+        .name
+        .to_owned();
+
+    let inventory = world
+        .resource::<Inventory>()
+        .0
+        .iter()
+        .filter_map(|entity| entity_path(world, *entity))
+        .collect();
+
+    let items = world
+        .query_filtered::<(Entity, Option<&Parent>), With<Portable>>()
+        .iter(world)
+        .filter_map(|(entity, parent)| {
+            let parent = match parent {
+                Some(parent) => Some(entity_path(world, parent.get())?),
+                None => None,
+            };
+            Some((entity_path(world, entity)?, parent))
+        })
+        .collect();
+
+    let health = world
+        .query::<(Entity, &Health)>()
+        .iter(world)
+        .filter_map(|(entity, health)| Some((entity_path(world, entity)?, health.0)))
+        .collect();
+
+    let saved_at = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |time| time.as_secs());
+
+    SaveFile {
+        version: SAVE_VERSION,
+        label: label.into(),
+        saved_at,
+        play_time: world.resource::<PlayTime>().0.as_secs(),
+        room,
+        autoprompt: world.resource::<IOManager>().autoprompt,
+        flags: world.resource::<Flags>().clone(),
+        inventory,
+        items,
+        health,
+    }
+}
+
+/// Save the game state to a file, labelled `label`
+pub fn save_game(
+    world: &mut World,
+    path: impl AsRef<Path>,
+    label: impl Into<String>,
+) -> Result<(), SaveError> {
+    let path = path.as_ref();
+    let save = snapshot(world, label);
+    let text = ron::ser::to_string_pretty(&save, ron::ser::PrettyConfig::default())
+        .map_err(SaveError::Serialize)?;
+
+    if let Some(directory) = path.parent() {
+        fs::create_dir_all(directory)?;
+    }
+    fs::write(path, text)?;
+    Ok(())
+}
+
+/// Save the game state to a slot in the [SaveSettings] directory, overwriting it if it exists
+pub fn save_slot(world: &mut World, slot: &str, label: impl Into<String>) -> Result<(), SaveError> {
+    let path = world.resource::<SaveSettings>().slot_path(slot);
+    save_game(world, path, label)
+}
+
+// ========== LOAD ==========
+
+/// Restore the game state from a [SaveFile].
+///
+/// The world is rebuilt with the game's [world builders](crate::plugin::AdventureApp::add_world_builder)
+/// first, the same way restarting does, so entities that died or were despawned since the save
+/// was made come back. Games without world builders can't be rebuilt, so the save is applied to
+/// the world as it is.
+///
+/// The room's [OnEnterRoom](crate::components::OnEnterRoom) event is *not* fired, since
+/// the player is already in that room. If anything in the save can't be found, the game is put
+/// back how it was (as far as a save can tell) and an error is returned.
+pub fn restore(world: &mut World, save: SaveFile) -> Result<(), SaveError> {
+    if save.version != SAVE_VERSION {
+        return Err(SaveError::Version {
+            found: save.version,
+            expected: SAVE_VERSION,
+        });
+    }
+
+    if world.resource::<WorldBuilders>().0.is_empty() {
+        return apply(world, save);
+    }
+
+    let current = snapshot(world, "");
+    systems::rebuild_world(world);
+    if let Err(err) = apply(world, save) {
+        systems::rebuild_world(world);
+        // If even this doesn't fit (like when the player is carrying something a handler
+        //  spawned), the game is left freshly rebuilt
+        let _ = apply(world, current);
+        return Err(err);
+    }
+    Ok(())
+}
+
+// Apply a save to the world as it is. If anything in the save can't be found, nothing is changed.
+fn apply(world: &mut World, save: SaveFile) -> Result<(), SaveError> {
+    // Find everything before changing anything, so a bad save doesn't leave a half-loaded game
+    let room = world
+        .query::<(Entity, &Room)>()
+        .iter(world)
+        .find(|(_, room)| room.name == save.room)
+        .map(|(entity, _)| entity)
+        .ok_or_else(|| SaveError::MissingRoom(save.room.clone()))?;
+
+    let entities = entity_paths(world);
+    let find = |path: &EntityPath| {
+        find_entity(&entities, path).ok_or_else(|| SaveError::MissingEntity(path.clone()))
+    };
+    let inventory = save
+        .inventory
+        .iter()
+        .map(find)
+        .collect::<Result<Vec<_>, _>>()?;
+    let items = save
+        .items
+        .iter()
+        .map(|(item, parent)| Ok((find(item)?, parent.as_ref().map(find).transpose()?)))
+        .collect::<Result<Vec<_>, SaveError>>()?;
+    let health = save
+        .health
+        .iter()
+        .map(|(path, health)| Ok((find(path)?, *health)))
+        .collect::<Result<HashMap<_, _>, SaveError>>()?;
+
+    world.resource_mut::<Flags>().replace(save.flags);
+    flags::sync_checkpoints(world);
+    world.resource_mut::<Inventory>().0 = inventory;
+
+    // Put items back where they were, before anything is looked up by its path again
+    for (item, parent) in items {
+        if let Some(current) = world.get::<Parent>(item).map(Parent::get) {
+            world.entity_mut(current).remove_children(&[item]);
+        }
+        if let Some(parent) = parent {
+            world.entity_mut(parent).push_children(&[item]);
+        }
+    }
+
+    // Restore health; anything with health that isn't in the save had died
+    let living = world
+        .query::<(Entity, &Health)>()
+        .iter(world)
+        .map(|(entity, _)| entity)
+        .collect::<Vec<_>>();
+    for entity in living {
+        match health.get(&entity) {
+            Some(health) => world.get_mut::<Health>(entity).unwrap().set_health(*health),
+            // Entities without a path can't be saved, so leave them alone
+            None if entity_path(world, entity).is_some() => {
+                world.entity_mut(entity).despawn_recursive()
+            }
+            None => {}
+        }
+    }
+
+    // Move the ActiveRoom marker, without firing the room's OnEnterRoom event
+    move_active_room(world, room);
+
+    world.resource_mut::<IOManager>().autoprompt = save.autoprompt;
+    world.resource_mut::<PlayTime>().0 = Duration::from_secs(save.play_time);
+    // Undoing past a load would mix the two games together
+    world.resource_mut::<UndoHistory>().clear();
+
+    Ok(())
+}
+
+/// Read a save file, without loading it
+pub fn read_save(path: impl AsRef<Path>) -> Result<SaveFile, SaveError> {
+    ron::from_str(&fs::read_to_string(path)?).map_err(SaveError::Format)
+}
+
+/// Load the game state from a file (see [restore])
+pub fn load_game(world: &mut World, path: impl AsRef<Path>) -> Result<(), SaveError> {
+    restore(world, read_save(path)?)
+}
+
+/// Load the game state from a slot in the [SaveSettings] directory
+pub fn load_slot(world: &mut World, slot: &str) -> Result<(), SaveError> {
+    let path = world.resource::<SaveSettings>().slot_path(slot);
+    load_game(world, path)
+}
+
+// ========== SAVE SLOTS ==========
+
+/// List the save slots in a directory, newest first. Files that aren't valid saves are skipped.
+pub fn list_slots(directory: impl AsRef<Path>) -> Vec<SaveSlot> {
+    let entries = match fs::read_dir(directory) {
+        Ok(entries) => entries,
+        // No directory means no saves yet
+        Err(_) => return Vec::new(),
+    };
+
+    let mut slots: Vec<SaveSlot> = entries
+        .filter_map(|entry| {
+            let path = entry.ok()?.path();
+            if path.extension()? != SAVE_EXTENSION {
+                return None;
+            }
+            Some(SaveSlot {
+                id: path.file_stem()?.to_str()?.to_owned(),
+                save: read_save(&path).ok()?,
+            })
+        })
+        .collect();
+    slots.sort_by_key(|slot| Reverse(slot.save.saved_at));
+    slots
+}
+
+/// Let the player pick a slot to save in - either a new slot, or overwriting an existing one.
+///
+/// Uses [IOManager::options_prompt]. New slots ask the player for a label.
+pub fn save_prompt(world: &mut World) {
+    let directory = world.resource::<SaveSettings>().directory.clone();
+    let slots = list_slots(&directory);
+    let iomgr = world.resource::<IOManager>();
+
+    // The first option makes a new slot, the rest overwrite existing ones
+    iomgr.println("Where do you want to save?");
+    let mut choices = vec!["New save".to_owned()];
+    choices.extend(slots.iter().map(|slot| slot.save.summary()));
+    choices.push("Cancel".to_owned());
+    let choice = usize::from(iomgr.options_prompt(choices.iter().map(String::as_str).collect()));
+
+    let (slot, label) = if choice == 1 {
+        let label = iomgr.prompt_raw("Name this save");
+        (new_slot_id(&slots), label)
+    } else if choice <= slots.len() + 1 {
+        let existing = &slots[choice - 2];
+        iomgr.println(format!("Overwrite '{}'?", existing.save.label));
+        if !iomgr.yes_no_prompt() {
+            return;
+        }
+        (existing.id.clone(), existing.save.label.clone())
+    } else {
+        return;
+    };
+
+    match save_slot(world, &slot, label) {
+        Ok(()) => world.resource::<IOManager>().println("Game saved."),
+        Err(err) => world
+            .resource::<IOManager>()
+            .println(format!("Couldn't save the game: {}", err)),
+    }
+}
+
+/// Let the player pick a save slot to load, with [IOManager::options_prompt]
+pub fn load_prompt(world: &mut World) {
+    let directory = world.resource::<SaveSettings>().directory.clone();
+    let slots = list_slots(&directory);
+    let iomgr = world.resource::<IOManager>();
+
+    if slots.is_empty() {
+        iomgr.println("There are no saves to load.");
+        return;
+    }
+
+    iomgr.println("Which save do you want to load?");
+    let mut choices: Vec<String> = slots.iter().map(|slot| slot.save.summary()).collect();
+    choices.push("Cancel".to_owned());
+    let choice = usize::from(iomgr.options_prompt(choices.iter().map(String::as_str).collect()));
+
+    if let Some(slot) = choice.checked_sub(1).and_then(|index| slots.get(index)) {
+        match load_slot(world, &slot.id) {
+            Ok(()) => world.resource::<IOManager>().println("Game loaded."),
+            Err(err) => world
+                .resource::<IOManager>()
+                .println(format!("Couldn't load the game: {}", err)),
+        }
+    }
+}
+
+// The first `save-N` slot ID that isn't taken
+fn new_slot_id(slots: &[SaveSlot]) -> String {
+    (1..)
+        .map(|index| format!("save-{}", index))
+        .find(|id| !slots.iter().any(|slot| slot.id == *id))
+        .unwrap()
+}
+
+// Format a play time in seconds, like `1h 5m`
+fn format_play_time(seconds: u64) -> String {
+    let hours = seconds / 3600;
+    let minutes = seconds % 3600 / 60;
+    if hours > 0 {
+        format!("{}h {}m", hours, minutes)
+    } else {
+        format!("{}m", minutes)
+    }
+}
+
+// Format a Unix timestamp as a UTC date & time, like `2023-01-15 18:30 UTC`
+fn format_timestamp(timestamp: u64) -> String {
+    let days = (timestamp / 86400) as i64;
+    let seconds = timestamp % 86400;
+
+    // Convert days since the epoch to a civil date
+    // (http://howardhinnant.github.io/date_algorithms.html#civil_from_days)
+    let z = days + 719468;
+    let era = z.div_euclid(146097);
+    let day_of_era = z.rem_euclid(146097);
+    let year_of_era =
+        (day_of_era - day_of_era / 1460 + day_of_era / 36524 - day_of_era / 146096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let shifted_month = (5 * day_of_year + 2) / 153;
+    let day = day_of_year - (153 * shifted_month + 2) / 5 + 1;
+    let month = if shifted_month < 10 {
+        shifted_month + 3
+    } else {
+        shifted_month - 9
+    };
+    let year = year_of_era + era * 400 + i64::from(month <= 2);
+
+    format!(
+        "{:04}-{:02}-{:02} {:02}:{:02} UTC",
+        year,
+        month,
+        day,
+        seconds / 3600,
+        seconds % 3600 / 60
+    )
+}
+
+// ========== ENTITY PATHS ==========
+
+// The name of a single entity, from its Name or Room component
+fn own_name(world: &World, entity: Entity) -> Option<String> {
+    if let Some(name) = world.get::<Name>(entity) {
+        Some(name.0.to_owned())
+    } else {
+        world.get::<Room>(entity).map(|room| room.name.to_owned())
+    }
+}
+
+/// Get the [EntityPath] of an entity, if it has a [Name] or is a [Room]
+pub fn entity_path(world: &World, entity: Entity) -> Option<EntityPath> {
+    let mut path = vec![own_name(world, entity)?];
+
+    // Walk up the hierarchy, adding any named ancestors
+    let mut current = entity;
+    while let Some(parent) = world.get::<Parent>(current) {
+        current = parent.get();
+        if let Some(name) = own_name(world, current) {
+            path.push(name);
+        }
+    }
+
+    path.reverse();
+    Some(EntityPath(path))
+}
+
+// The EntityPath of every named entity in the world
+fn entity_paths(world: &mut World) -> Vec<(EntityPath, Entity)> {
+    let entities = world.query::<Entity>().iter(world).collect::<Vec<_>>();
+    entities
+        .into_iter()
+        .filter_map(|entity| Some((entity_path(world, entity)?, entity)))
+        .collect()
+}
+
+// Find an entity by its path. Items may have been moved around the hierarchy since
+// they were saved, so if there's no exact match, fall back to a unique entity with
+// the same name.
+fn find_entity(entities: &[(EntityPath, Entity)], path: &EntityPath) -> Option<Entity> {
+    if let Some((_, entity)) = entities.iter().find(|(test, _)| test == path) {
+        return Some(*entity);
+    }
+
+    let name = path.0.last()?;
+    let mut matches = entities
+        .iter()
+        .filter(|(test, _)| test.0.last() == Some(name));
+    match (matches.next(), matches.next()) {
+        (Some((_, entity)), None) => Some(*entity),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::env;
+
+    fn save(label: &str, saved_at: u64) -> SaveFile {
+        SaveFile {
+            version: SAVE_VERSION,
+            label: label.to_owned(),
+            saved_at,
+            play_time: 0,
+            room: "Museum".to_owned(),
+            autoprompt: true,
+            flags: Flags::default(),
+            inventory: Vec::new(),
+            items: Vec::new(),
+            health: Vec::new(),
+        }
+    }
+
+    fn slot(id: &str) -> SaveSlot {
+        SaveSlot {
+            id: id.to_owned(),
+            save: save(id, 0),
+        }
+    }
+
+    #[test]
+    fn slots_are_listed_newest_first() {
+        let directory = env::temp_dir()
+            .join("adventure_save_slots")
+            .join(std::process::id().to_string());
+        let _ = fs::remove_dir_all(&directory);
+        fs::create_dir_all(&directory).unwrap();
+
+        let settings = SaveSettings::in_directory(&directory);
+        for (id, saved_at) in [("save-1", 100), ("save-2", 300), ("save-3", 200)] {
+            let text = ron::to_string(&save(id, saved_at)).unwrap();
+            fs::write(settings.slot_path(id), text).unwrap();
+        }
+        // Other files are skipped
+        fs::write(directory.join("notes.txt"), "not a save").unwrap();
+        fs::write(settings.slot_path("broken"), "not a save either").unwrap();
+
+        let ids: Vec<String> = list_slots(&directory)
+            .into_iter()
+            .map(|slot| slot.id)
+            .collect();
+        assert_eq!(ids, vec!["save-2", "save-3", "save-1"]);
+
+        fs::remove_dir_all(&directory).unwrap();
+    }
+
+    #[test]
+    fn slot_ids_can_have_dots() {
+        let settings = SaveSettings::in_directory("saves");
+        assert_eq!(
+            settings.slot_path("v1.2"),
+            PathBuf::from("saves").join("v1.2.sav")
+        );
+        assert_ne!(settings.slot_path("v1.2"), settings.slot_path("v1.3"));
+    }
+
+    #[test]
+    fn missing_directories_have_no_slots() {
+        let directory = env::temp_dir().join("adventure_save_slots_that_dont_exist");
+        assert!(list_slots(directory).is_empty());
+    }
+
+    #[test]
+    fn new_slot_ids_fill_gaps() {
+        assert_eq!(new_slot_id(&[]), "save-1");
+        assert_eq!(new_slot_id(&[slot("save-1"), slot("save-2")]), "save-3");
+        assert_eq!(new_slot_id(&[slot("save-3"), slot("save-1")]), "save-2");
+        // Slots the player named some other way don't get in the way
+        assert_eq!(new_slot_id(&[slot("autosave")]), "save-1");
+    }
+}
@@ -3,29 +3,48 @@ use std::sync::{Arc, Mutex};
 use bevy::ecs::system::SystemState;
 use bevy::prelude::{
     Added, App, Children, CoreStage, Entity, EventReader, HierarchyQueryExt, IntoSystemDescriptor,
-    Mut, Query, Res, ResMut, Resource, StartupStage, SystemSet, With, World,
+    Mut, Or, Query, Res, ResMut, Resource, StartupStage, SystemSet, Time, With, Without, World,
 };
 
-use crate::components::{Aliases, Name};
+use crate::adventure_commands::try_set_room;
+use crate::components::{
+    Aliases, Description, Direction, DynamicDescription, Exits, Hidden, Name, Portable, Pronouns,
+};
+use crate::conditions::Condition;
+use crate::description;
+use crate::error;
+use crate::flags::{self, Flags};
+use crate::parser::{
+    InteractionContext, LastMentioned, PlayerCommand, Suggestion, Suggestions, PREPOSITIONS,
+};
+use crate::player::{self, Checkpoints, Inventory, PlayTime};
+use crate::plugin::WorldBuilders;
+use crate::rooms::{RoomId, RoomIndex};
+use crate::save;
+use crate::undo::{self, UndoHistory};
 use crate::{
-    components::{ActiveRoom, Health, OnDeath, OnEnterRoom, OnInteract, Room},
+    components::{ActiveRoom, Health, Level, OnDeath, OnEnterRoom, OnInteract, Refusal, Room},
     events::EventHandler,
-    input_output_manager::{IOManager, WordType, KEYWORDS},
+    input_output_manager::{IOManager, Vocabulary, WordType},
 };
+use bevy::hierarchy::DespawnRecursiveExt;
+use std::time::Duration;
 
 // ========== ADD SYSTEMS TO APP ==========
 
 /// Appends all of bevy_adventure's systems to the app
 pub fn append_systems(app: &mut App) {
-    // Init events at startup
+    // Build the world at startup
+    app.add_startup_system(build_world);
+    // Index rooms & init events at startup
+    app.add_startup_system_to_stage(StartupStage::PostStartup, RoomIndex::rebuild);
     app.add_startup_system_to_stage(StartupStage::PostStartup, init_events);
     // A generic system listening for Bevy's exit
     app.add_system(on_exit);
+    // Count how long the player has played for
+    app.add_system(track_play_time);
     // All the other systems
     app.add_system_set_to_stage(CoreStage::PreUpdate, build_system_set());
-    // In development, add code-checking systems
-    #[cfg(debug_assertions)]
-    app.add_startup_system_set_to_stage(StartupStage::PostStartup, build_debug_system_set());
 }
 
 // ========== NORMAL SYSTEMS ==========
@@ -33,8 +52,10 @@ pub fn append_systems(app: &mut App) {
 // The normal systems in bevy_adventure
 fn build_system_set() -> SystemSet {
     SystemSet::new()
-        // First, handle dead entities
-        .with_system(handle_dead)
+        // First, sync Checkpoints with Flags & send their events
+        .with_system(flags::update_flags)
+        // Then, handle dead entities
+        .with_system(handle_dead.after(flags::update_flags))
         // Then, trigger any new room events
         .with_system(new_room_event.after(handle_dead))
         // Finally, get user input
@@ -68,9 +89,9 @@ fn handle_dead(world: &mut World) {
         event.lock().unwrap().fire(world);
     }
 
-    // Now remove the dead entities
+    // Now remove the dead entities (keeping them for undo, if it's on)
     dead.iter().for_each(|entity| {
-        world.despawn(*entity);
+        undo::bury(world, *entity);
     })
 }
 
@@ -84,14 +105,35 @@ impl NewRoomState {
     }
 }
 
+/// Mark the current ActiveRoom as already entered, so its OnEnterRoom event won't fire
+pub(crate) fn skip_new_room_event(world: &mut World) {
+    world.resource_scope(|world, mut state: Mut<NewRoomState>| {
+        // Fetching the query updates its change ticks, so the `Added<ActiveRoom>` is consumed
+        state.0.get_mut(world);
+    });
+}
+
+/// Make `room` the active room without firing its OnEnterRoom event (for restoring saved state)
+pub(crate) fn move_active_room(world: &mut World, room: Entity) {
+    let active_rooms = world
+        .query_filtered::<Entity, With<ActiveRoom>>()
+        .iter(world)
+        .collect::<Vec<_>>();
+    for entity in active_rooms {
+        world.entity_mut(entity).remove::<ActiveRoom>();
+    }
+    world.entity_mut(room).insert(ActiveRoom);
+    skip_new_room_event(world);
+}
+
 // When the player enters a new room that has an OnEnterRoom handler
 fn new_room_event(world: &mut World) {
     // Get our cached SystemState
     world.resource_scope(|world, mut state: Mut<NewRoomState>| {
         // Get our query
         let (query, mut iomgr) = state.0.get_mut(world);
-        // Event, if it exists
-        let mut event = None;
+        // Event handlers, if there are any
+        let mut handlers = None;
 
         // See if there is a new room
         match query.get_single() {
@@ -99,17 +141,17 @@ fn new_room_event(world: &mut World) {
             Err(_) => {}
             // If there is a new room, fire the appropriate events (if they're registered)
             Ok(on_enter) => {
-                // If it has an on_enter event handler, run the handler
-                if let Some(handler) = on_enter {
-                    event = Some(handler.0.clone());
+                // If it has on_enter event handlers, run the first one whose condition passes
+                if let Some(on_enter) = on_enter {
+                    handlers = Some(on_enter.0.clone());
                 }
                 // Also disable AutoPrompt so it's only on if set manually
                 iomgr.autoprompt = false;
             }
         }
 
-        // If there was an event handler, fire it
-        if let Some(handler) = event {
+        // If there was an event handler that can run, fire it
+        if let Some(handler) = handlers.and_then(|handlers| handlers.find(world)) {
             handler.lock().unwrap().fire(world);
         }
     });
@@ -122,128 +164,668 @@ fn on_exit(exit: EventReader<bevy::app::AppExit>, mut iomgr: ResMut<IOManager>)
     }
 }
 
+// Add the time since the last frame to the player's play time
+fn track_play_time(time: Res<Time>, mut play_time: ResMut<PlayTime>) {
+    play_time.0 += time.delta();
+}
+
 // Player autoprompt
 fn player_input(world: &mut World) {
-    // Query types
-    type ActiveRoomQuery<'world, 'state, 'a> =
-        Query<'world, 'state, (Entity, &'a Room), With<ActiveRoom>>;
+    // If AutoPrompt is disabled, return
+    if !world.resource::<IOManager>().autoprompt {
+        return;
+    }
+
+    let active_room = world
+        .query_filtered::<Entity, With<ActiveRoom>>()
+        .single(world);
+
+    // Print the room's description, unless the player just looked around
+    let room_described = std::mem::take(&mut world.resource_mut::<IOManager>().room_described);
+    if !room_described {
+        if let Some(desc) = description::describe(world, active_room) {
+            world.resource::<IOManager>().println(desc);
+        }
+    }
+
+    // Prompt the player for input
+    let input = world.resource::<IOManager>().prompt_raw("What do you do?");
+    handle_input(world, &input);
+    // Sync anything the player's command changed
+    flags::update_flags(world);
+}
+
+// Act on a line of the player's input
+fn handle_input(world: &mut World, input: &str) {
+    let active_room_entity = world
+        .query_filtered::<Entity, With<ActiveRoom>>()
+        .single(world);
+
+    // Handle commands that control the game itself, rather than the story
+    if let Some(command) = MetaCommand::parse(input) {
+        command.run(world);
+        return;
+    }
+
+    // Move the player if they're taking one of the room's exits
+    if let Some(travel) = find_exit(world, active_room_entity, input) {
+        match travel {
+            Travel::To(room) => {
+                undo::record(world);
+                if let Err(err) = try_set_room(world, room) {
+                    // Nothing changed, so there's nothing to undo
+                    world.resource_mut::<UndoHistory>().pop();
+                    error::report_error(world, err);
+                }
+            }
+            Travel::Blocked(message) => world.resource::<IOManager>().println(message),
+        }
+        return;
+    }
+
+    let candidates = candidates(world, active_room_entity);
+
+    // "them" means every entity the player last mentioned, so the command is done to each one
+    let them = them(world, input, &candidates);
+    let commands: Vec<PlayerCommand> = if them.is_empty() {
+        vec![parse_input(world, active_room_entity, input, &candidates)]
+    } else {
+        them.iter()
+            .map(|entity| {
+                let mut candidates = candidates.clone();
+                candidates.push((*entity, vec!["them"]));
+                parse_input(world, active_room_entity, input, &candidates)
+            })
+            .collect()
+    };
+
+    // Remember what the player mentioned, for pronouns ("them" still means the same entities)
+    if them.is_empty() {
+        world.resource_mut::<LastMentioned>().update(&commands[0]);
+    }
+
+    // Remember the game's state before this command, so it can be undone
+    undo::record(world);
+
+    let command_count = commands.len();
+    let mut unhandled = Vec::new();
+    for command in commands {
+        if let Outcome::Unhandled(reply) = run_command(world, command.clone(), active_room_entity) {
+            unhandled.push((command, reply));
+        }
+    }
+    if unhandled.len() == command_count {
+        // Nothing changed, so there's nothing to undo
+        world.resource_mut::<UndoHistory>().pop();
+    }
+
+    for (command, reply) in unhandled {
+        // Nothing understood the command, so the player probably made a typo - tell them
+        //  (and maybe fix it), or give the usual reply if they don't want the fix
+        if suggest(world, active_room_entity, input, &command, &candidates) {
+            continue;
+        }
+        if let Some(reply) = reply {
+            world.resource::<IOManager>().println(reply);
+        }
+    }
+}
+
+// Break the player's input into a command, asking them which entity they meant if it's ambiguous
+fn parse_input(
+    world: &World,
+    room: Entity,
+    input: &str,
+    candidates: &[(Entity, Vec<&'static str>)],
+) -> PlayerCommand {
+    let vocabularies = vocabularies(world, room);
+    let iomgr = world.resource::<IOManager>();
+    PlayerCommand::parse_with(input, candidates, &vocabularies, |entities| {
+        // Ask the player which entity they meant
+        let labels = choice_labels(world, entities, candidates);
+        let labels: Vec<&str> = labels.iter().map(String::as_str).collect();
+        iomgr.println(format!("Which do you mean: {}?", list_names(&labels, "or")));
+        entities[usize::from(iomgr.options_prompt(labels)) - 1]
+    })
+}
+
+// What to call each of `entities` when asking the player which one they mean: its name, unless
+//  another one has the same name. Then it's an alias none of the others have, its description,
+//  or whether the player is carrying it, in that order.
+fn choice_labels(
+    world: &World,
+    entities: &[Entity],
+    candidates: &[(Entity, Vec<&'static str>)],
+) -> Vec<String> {
+    let names_of = |entity: Entity| -> &[&'static str] {
+        candidates
+            .iter()
+            .find(|(candidate, _)| *candidate == entity)
+            .map_or(&[], |(_, names)| names.as_slice())
+    };
+    let shared = |entity: Entity, name: &str| {
+        entities
+            .iter()
+            .filter(|other| **other != entity)
+            .flat_map(|other| names_of(*other))
+            .any(|other_name| other_name.eq_ignore_ascii_case(name))
+    };
+
+    entities
+        .iter()
+        .map(|entity| {
+            let names = names_of(*entity);
+            let name = names.first().copied().unwrap_or_default();
+            if !shared(*entity, name) {
+                name.to_owned()
+            } else if let Some(alias) = names.iter().find(|alias| !shared(*entity, alias)) {
+                (*alias).to_owned()
+            } else if let Some(description) = world.get::<Description>(*entity) {
+                format!("{} - {}", name, description.0)
+            } else if world.resource::<Inventory>().0.contains(entity) {
+                format!("{} (carried)", name)
+            } else {
+                name.to_owned()
+            }
+        })
+        .collect()
+}
+
+// The entities "them" refers to in the player's input: everything they last mentioned, if that
+// was more than one thing. Empty if they didn't say "them", or if something's called "them".
+fn them(world: &World, input: &str, candidates: &[(Entity, Vec<&'static str>)]) -> Vec<Entity> {
+    let said_them = input
+        .split_whitespace()
+        .any(|word| word.eq_ignore_ascii_case("them"));
+    let named_them = candidates
+        .iter()
+        .flat_map(|(_, names)| names)
+        .any(|name| name.eq_ignore_ascii_case("them"));
+    if !said_them || named_them {
+        return Vec::new();
+    }
+
+    let them: Vec<Entity> = world
+        .resource::<LastMentioned>()
+        .0
+        .iter()
+        .copied()
+        .filter(|entity| candidates.iter().any(|(candidate, _)| candidate == entity))
+        .collect();
+    if them.len() > 1 {
+        them
+    } else {
+        Vec::new()
+    }
+}
+
+// Run the handlers for a command the player typed
+fn run_command(world: &mut World, command: PlayerCommand, active_room_entity: Entity) -> Outcome {
+    let verb = command.verb.clone();
+    // The indirect object is usually what the action is done to ("give bible to joseph"),
+    //  except for "with", where it's what the action is done with ("open door with key")
+    let indirect_object = command
+        .indirect_object
+        .filter(|_| command.preposition != Some("with"));
+    // If we haven't identified the target already, fall back to the room
+    let target = command.direct_object.unwrap_or(active_room_entity);
+
+    // Let handlers see the whole command
+    world.insert_resource(command.clone());
+
+    // If the action is done to the indirect object, and it handles this action, it goes first
+    if let Some(entity) = indirect_object {
+        let handler = verb
+            .as_ref()
+            .and_then(|verb| find_handler(world, entity, verb))
+            .or_else(|| find_handler(world, entity, &WordType::Any));
+        if let Some(handler) = handler {
+            let context = InteractionContext::new(&command, entity);
+            handler.lock().unwrap().fire_with(world, context);
+            return Outcome::Handled;
+        }
+    }
+
+    let context = InteractionContext::new(&command, target);
+
+    // The target's handler for this action goes first
+    if let Some(handler) = verb
+        .as_ref()
+        .and_then(|verb| find_handler(world, target, verb))
+    {
+        handler.lock().unwrap().fire_with(world, context);
+        return Outcome::Handled;
+    }
+
+    // Portable items can be taken & dropped, unless they have their own handler for it
+    if let Some(verb @ (WordType::Take | WordType::Drop)) = &verb {
+        if world.get::<Portable>(target).is_some() {
+            match verb {
+                WordType::Take => player::take_item(world, target),
+                _ => player::drop_item(world, target),
+            }
+            return Outcome::Handled;
+        }
+    }
+
+    // Looking at something describes it, unless it has its own handler for it
+    if verb == Some(WordType::Look) {
+        if target == active_room_entity {
+            describe_room(world, active_room_entity);
+            return Outcome::Handled;
+        }
+        if let Some(description) = description::describe(world, target) {
+            world.resource::<IOManager>().println(description);
+            return Outcome::Handled;
+        }
+    }
+
+    // WordType::Any is always a fallback
+    if let Some(handler) = find_handler(world, target, &WordType::Any) {
+        handler.lock().unwrap().fire_with(world, context);
+        return Outcome::Handled;
+    }
+
+    // See if the action target has an event
+    if let Some(event) = world.get::<OnInteract>(target) {
+        let had_handlers = verb
+            .iter()
+            .chain(std::iter::once(&WordType::Any))
+            .any(|word_type| event.0.contains_key(word_type));
+        if let (true, Some(refusal)) = (had_handlers, world.get::<Refusal>(target)) {
+            // The handlers' conditions all failed
+            world.resource::<IOManager>().println(refusal.0);
+        } else if verb.is_none() {
+            // If an action wasn't identified, and there's no handler for WordType::Any, just error out
+            return Outcome::Unhandled(Some("I don't understand that action."));
+        } else if verb == Some(WordType::Look) && !had_handlers {
+            nothing_special(world, target);
+        } else {
+            return Outcome::Unhandled(None);
+        }
+    } else if verb == Some(WordType::Look) && world.get::<Name>(target).is_some() {
+        // Things that don't do anything at all still deserve a response
+        nothing_special(world, target);
+    } else {
+        // If it doesn't, give a generic confusion message
+        return Outcome::Unhandled(Some("Sorry, that action is confusing."));
+    }
+    Outcome::Handled
+}
+
+// What happened when a command was run
+enum Outcome {
+    // Something handled the command
+    Handled,
+    // Nothing could handle the command, so the player gets this reply instead (if there is one)
+    Unhandled(Option<&'static str>),
+}
+
+// The response to looking at something with no description or Look handler
+fn nothing_special(world: &mut World, target: Entity) {
+    if let Some(name) = world.get::<Name>(target) {
+        world
+            .resource::<IOManager>()
+            .println(format!("You see nothing special about the {}.", name.0));
+    }
+}
+
+// Everything the player can talk about: what's in the room, and what's in their inventory
+fn candidates(world: &mut World, room: Entity) -> Vec<(Entity, Vec<&'static str>)> {
     type ChildrenQuery<'world, 'state, 'a> = Query<'world, 'state, &'a Children>;
     type NameQuery<'world, 'state, 'a> = Query<'world, 'state, (&'a Name, Option<&'a Aliases>)>;
-    type IOManagerResource<'w> = Res<'w, IOManager>;
+    type PronounQuery<'world, 'state, 'a> = Query<'world, 'state, Option<&'a Pronouns>>;
+    type InventoryResource<'w> = Res<'w, Inventory>;
+    type LastMentionedResource<'w> = Res<'w, LastMentioned>;
 
-    // This system's queries
-    let mut active_room_query: SystemState<(
-        ActiveRoomQuery,
+    let mut state: SystemState<(
         ChildrenQuery,
         NameQuery,
-        IOManagerResource,
+        PronounQuery,
+        InventoryResource,
+        LastMentionedResource,
     )> = SystemState::new(world);
+    let (children, names, pronouns, inventory, last_mentioned) = state.get(world);
+
+    let mut candidates = Vec::new();
+    for entity in children
+        .iter_descendants(room)
+        .chain(inventory.0.iter().copied())
+    {
+        // Items games put in the inventory themselves might still be in the room
+        if candidates.iter().any(|(candidate, _)| *candidate == entity) {
+            continue;
+        }
+        if let Ok((name, aliases)) = names.get(entity) {
+            let mut all_names = vec![name.0];
+            all_names.extend(aliases.iter().flat_map(|aliases| aliases.0.iter().copied()));
+            candidates.push((entity, all_names));
+        }
+    }
 
-    // Get queries
-    let (active_room_query, children, names, iomgr) = active_room_query.get(world);
+    // The last things the player mentioned can also be called by their pronouns. These go last,
+    // so if a pronoun is also something's name, the name wins. ("them" for all of them together
+    // is handled by handle_input, since it means several entities at once.)
+    for entity in &last_mentioned.0 {
+        if candidates.iter().any(|(candidate, _)| candidate == entity) {
+            let entity_pronouns = match pronouns.get(*entity) {
+                Ok(Some(pronouns)) => pronouns.0.clone(),
+                _ => vec!["it"],
+            };
+            candidates.push((*entity, entity_pronouns));
+        }
+    }
+    candidates
+}
 
-    // If AutoPrompt is disabled, return
-    if !iomgr.autoprompt {
-        return;
+// The first of an entity's handlers for a word type whose condition passes
+fn find_handler(
+    world: &mut World,
+    entity: Entity,
+    word_type: &WordType,
+) -> Option<Arc<Mutex<dyn EventHandler>>> {
+    let handlers = world
+        .get::<OnInteract>(entity)
+        .and_then(|event| event.0.get(word_type))
+        .cloned()?;
+    handlers.find(world)
+}
+
+// Print a room's description, and the visible, named things in it
+fn describe_room(world: &mut World, room: Entity) {
+    type ChildrenQuery<'world, 'state, 'a> = Query<'world, 'state, &'a Children>;
+    type VisibleQuery<'world, 'state, 'a> = Query<'world, 'state, &'a Name, Without<Hidden>>;
+
+    let mut state: SystemState<(ChildrenQuery, VisibleQuery)> = SystemState::new(world);
+    let (children, visible) = state.get(world);
+    let names: Vec<&str> = children
+        .get(room)
+        .iter()
+        .flat_map(|children| children.iter())
+        .filter_map(|child| visible.get(*child).ok())
+        .map(|name| name.0)
+        .collect();
+
+    let description = description::describe(world, room);
+    let mut iomgr = world.resource_mut::<IOManager>();
+    let has_description = description.is_some();
+    if let Some(description) = description {
+        iomgr.println(description);
+        // Don't print it again before the next prompt
+        iomgr.room_described = iomgr.autoprompt;
+    }
+    if !names.is_empty() {
+        iomgr.println(format!("You see: {}.", list_names(&names, "and")));
+    } else if !has_description {
+        iomgr.println("There's nothing to see here.");
     }
+}
 
-    let (active_room_entity, active_room) = active_room_query.single();
+// List names like "a, b or c" (or "a, b and c")
+pub(crate) fn list_names(names: &[&str], conjunction: &str) -> String {
+    match names {
+        [] => String::new(),
+        [name] => name.to_string(),
+        [rest @ .., last] => format!("{} {} {}", rest.join(", "), conjunction, last),
+    }
+}
 
-    // Print the room's description
-    if let Some(desc) = &active_room.description {
-        iomgr.println(desc);
+// Tell the player about a probable typo in a command nothing could handle. With
+//  Suggestions::Prompt, they can accept the fix. Returns false if there's nothing to suggest,
+//  or the player turned the fix down.
+fn suggest(
+    world: &mut World,
+    room: Entity,
+    input: &str,
+    command: &PlayerCommand,
+    candidates: &[(Entity, Vec<&'static str>)],
+) -> bool {
+    let suggestions = *world.resource::<Suggestions>();
+    if suggestions == Suggestions::Off {
+        return false;
+    }
+    let Some(suggestion) = Suggestion::find(command, candidates, &vocabularies(world, room)) else {
+        return false;
+    };
+
+    let iomgr = world.resource::<IOManager>();
+    if suggestion.is_verb {
+        iomgr.println(format!(
+            "I don't know how to '{}'. Did you mean '{}'?",
+            suggestion.word, suggestion.replacement
+        ));
+    } else {
+        iomgr.println(format!(
+            "I don't see a '{}' here. Did you mean '{}'?",
+            suggestion.word, suggestion.replacement
+        ));
     }
 
-    // Prompt the player for input
-    let input = iomgr.prompt_raw("What do you do?");
-
-    // Parse the input
-    let split = input.split_whitespace();
-
-    let mut potential_targets = Vec::new();
-    let mut action: Option<WordType> = None;
-
-    // Iterate through words and see if they are keywords or not
-    split.for_each(|word| {
-        match KEYWORDS.get(&word.to_lowercase()) {
-            // If it isn't a keyword, it might be a target
-            None => potential_targets.push(word),
-            // If it is a keyword,
-            Some(word_type) => match word_type {
-                // Either ignore it
-                WordType::Ignore => {}
-                // Or set it as the action.
-                _ => {
-                    if action.is_none() {
-                        action = Some(word_type.clone());
-                    }
-                }
-            },
+    if suggestions == Suggestions::Prompt {
+        if !iomgr.yes_no_prompt() {
+            return false;
         }
-    });
+        handle_input(world, &suggestion.apply(input));
+    }
+    true
+}
 
-    // Try and get the target of the action
-    // Iterate through the active children
-    let mut target = children.iter_descendants(active_room_entity).find(|child| {
-        if let Ok((name, aliases)) = names.get(*child) {
-            // Iterate through potential targets
-            potential_targets
-                .iter()
-                // See if any of the potential targets match this entity's name
-                .any(|test_name| {
-                    // Also see if the entity has aliases to check
-                    if let Some(alias_list) = aliases {
-                        (alias_list.0.contains(test_name)) || (*test_name == name.0)
-                    } else {
-                        // If not, just check the entity name
-                        *test_name == name.0
-                    }
-                })
-        } else {
-            false
+// The vocabularies to parse input with in a room: the room's own words first, then the game's
+fn vocabularies(world: &World, room: Entity) -> Vec<&Vocabulary> {
+    world
+        .get::<Vocabulary>(room)
+        .into_iter()
+        .chain(std::iter::once(world.resource::<Vocabulary>()))
+        .collect()
+}
+
+// Printed when the player tries to go a direction the room has no exit in
+const NO_EXIT: &str = "You can't go that way.";
+
+// The result of the player trying to take one of a room's exits
+enum Travel {
+    // Go to this room
+    To(RoomId),
+    // The exit is blocked, print this message
+    Blocked(&'static str),
+}
+
+// See if the player's input is taking one of the room's exits.
+//
+// A bare direction ("north", "n") or a movement verb with a direction or custom exit label
+// ("go north", "enter house") counts. Anything else returns None, so it's parsed normally.
+fn find_exit(world: &mut World, room: Entity, input: &str) -> Option<Travel> {
+    let exits = world.get::<Exits>(room)?;
+    let vocabularies = vocabularies(world, room);
+
+    // Split out movement verbs (go, enter, etc) and ignored words
+    let words: Vec<String> = input.split_whitespace().map(str::to_lowercase).collect();
+    let mut moving = false;
+    let mut rest: Vec<&str> = words
+        .iter()
+        .filter(|word| match Vocabulary::lookup(&vocabularies, word) {
+            Some(WordType::Move) => {
+                moving = true;
+                false
+            }
+            Some(WordType::Ignore) => false,
+            _ => true,
+        })
+        .map(String::as_str)
+        .collect();
+
+    // Prepositions before the destination aren't part of it ("go to the front door"),
+    //  but a lone "in" is still a direction
+    let leading = rest[..rest.len().saturating_sub(1)]
+        .iter()
+        .take_while(|word| PREPOSITIONS.contains(word))
+        .count();
+    rest.drain(..leading);
+
+    // Without a movement verb, only a single direction counts
+    if rest.is_empty() || (!moving && rest.len() > 1) {
+        return None;
+    }
+    let destination = rest.join(" ");
+
+    let exit = if let Some(direction) = Direction::parse(&destination) {
+        match exits.0.get(&direction) {
+            Some(exit) => exit,
+            None => return Some(Travel::Blocked(NO_EXIT)),
         }
-    });
+    } else if moving {
+        exits.custom(&destination)?.1
+    } else {
+        return None;
+    };
 
-    // If we haven't identified the target already, fall back to the room
-    if target.is_none() {
-        target = Some(active_room_entity);
+    // Conditions need the world mutably, so the exit can't be borrowed from it
+    let exit = exit.clone();
+    if exit.is_open(world) {
+        Some(Travel::To(exit.target))
+    } else {
+        Some(Travel::Blocked(exit.blocked_message.unwrap_or(NO_EXIT)))
     }
+}
 
-    // Act upon the player's input
-    // Get an EntityMut so we can see the target's components
-    let target_mut = world.entity(target.unwrap());
+// Commands that control the game itself (saving, loading, etc), rather than the story
+enum MetaCommand {
+    Save,
+    Load,
+    Undo,
+    Restart,
+    Inventory,
+    Help,
+}
+impl MetaCommand {
+    // See if the player's input is a meta-command
+    fn parse(input: &str) -> Option<Self> {
+        match input.to_lowercase().as_str() {
+            "save" => Some(Self::Save),
+            "load" => Some(Self::Load),
+            "undo" => Some(Self::Undo),
+            "restart" => Some(Self::Restart),
+            "inventory" | "i" => Some(Self::Inventory),
+            "help" => Some(Self::Help),
+            _ => None,
+        }
+    }
 
-    // See if the action target has an event
-    if let Some(event) = target_mut.get::<OnInteract>() {
-        // If it does, see if an action was identified
-        if let Some(action_type) = action {
-            // If we have an OnInteract even and action, make sure their types match
-            if let Some(handler) = event.0.get(&action_type) {
-                handler.clone().lock().unwrap().fire(world);
-            // WordType::Any is always a fallback
-            } else if let Some(handler) = event.0.get(&WordType::Any) {
-                handler.clone().lock().unwrap().fire(world);
+    fn run(self, world: &mut World) {
+        match self {
+            Self::Save => save::save_prompt(world),
+            Self::Load => save::load_prompt(world),
+            Self::Undo => match undo::undo(world) {
+                Ok(()) => world.resource::<IOManager>().println("Undone."),
+                Err(err) => world.resource::<IOManager>().println(err.to_string()),
+            },
+            Self::Restart => {
+                let iomgr = world.resource::<IOManager>();
+                if world.resource::<WorldBuilders>().0.is_empty() {
+                    iomgr.println(CANT_RESTART);
+                    return;
+                }
+                iomgr.println(
+                    "Are you sure you want to restart? All unsaved progress will be lost.",
+                );
+                if iomgr.yes_no_prompt() {
+                    restart_game(world);
+                }
             }
-        } else {
-            // If an action wasn't identified, see if the target has a handler for WordType::Any
-            if let Some(handler) = event.0.get(&WordType::Any) {
-                handler.clone().lock().unwrap().fire(world);
-            } else {
-                // If there isn't one, just error out
-                iomgr.println("I don't understand that action.");
+            Self::Inventory => player::list_inventory(world),
+            Self::Help => {
+                let room = world
+                    .query_filtered::<Entity, With<ActiveRoom>>()
+                    .single(world);
+                let vocabularies = vocabularies(world, room);
+
+                let mut help = String::from("You can:\n");
+                for (action, words) in Vocabulary::actions(&vocabularies) {
+                    help.push_str(&format!("- {} ({})\n", action.name(), words.join(", ")));
+                }
+                help.push_str("Or: inventory, save, load, undo, restart, help");
+                world.resource::<IOManager>().println(help);
             }
         }
-    } else {
-        // If it doesn't, print a generic confusion message
-        iomgr.println("Sorry, that action is confusing.");
     }
 }
 
 // ========== STARTUP SYSTEMS ==========
 
+// Run the systems registered with AdventureApp::add_world_builder
+fn build_world(world: &mut World) {
+    let builders = world.resource::<WorldBuilders>().0.clone();
+    for builder in builders {
+        let mut builder = builder.lock().unwrap();
+        builder.init(world);
+        builder.fire(world);
+    }
+}
+
+// Printed when the game has no world builders to rebuild itself with
+const CANT_RESTART: &str = "Sorry, this game can't be restarted.";
+
+/// Reset the game to how it was when it launched.
+///
+/// This despawns every adventure entity, clears the player's resources, and then re-runs
+/// the world builders. The starting room's OnEnterRoom event fires on the next update.
+///
+/// Games without world builders (that spawn their rooms in startup systems) can't be
+/// rebuilt, so the player is told that instead, and nothing changes.
+pub(crate) fn restart_game(world: &mut World) {
+    if world.resource::<WorldBuilders>().0.is_empty() {
+        world.resource::<IOManager>().println(CANT_RESTART);
+        return;
+    }
+    rebuild_world(world);
+}
+
+/// Despawn every adventure entity, clear the player's resources, and re-run the world builders
+/// (for restarting, or loading a save into a fresh world)
+pub(crate) fn rebuild_world(world: &mut World) {
+    // Despawn everything bevy_adventure knows about
+    type AdventureEntity = Or<(
+        With<Room>,
+        With<Level>,
+        With<Name>,
+        With<Health>,
+        With<OnInteract>,
+        With<OnDeath>,
+        With<OnEnterRoom>,
+        With<Portable>,
+    )>;
+    let entities = world
+        .query_filtered::<Entity, AdventureEntity>()
+        .iter(world)
+        .collect::<Vec<_>>();
+    for entity in entities {
+        // Children may have already been despawned with their parent
+        if world.get_entity(entity).is_some() {
+            world.entity_mut(entity).despawn_recursive();
+        }
+    }
+
+    // Reset the player
+    world.resource_mut::<Flags>().clear_all();
+    world.resource_mut::<Checkpoints>().0.clear();
+    flags::sync_checkpoints(world);
+    world.resource_mut::<Inventory>().0.clear();
+    world.resource_mut::<LastMentioned>().0.clear();
+    world.resource_mut::<PlayTime>().0 = Duration::ZERO;
+    world.resource_mut::<UndoHistory>().clear();
+    world.resource_mut::<IOManager>().autoprompt = false;
+
+    // Rebuild the world
+    let builders = world.resource::<WorldBuilders>().0.clone();
+    for builder in builders {
+        builder.lock().unwrap().fire(world);
+    }
+    init_events(world);
+    RoomIndex::rebuild(world);
+}
+
 // Initialize all Events (SystemFunctions must be initialized before use)
 fn init_events(world: &mut World) {
-    // Query types
     type OnDeathHandler<'a> = Option<&'a OnDeath>;
     type OnInteractionHandler<'a> = Option<&'a OnInteract>;
     type OnEnterRoomHandler<'a> = Option<&'a OnEnterRoom>;
@@ -252,8 +834,9 @@ fn init_events(world: &mut World) {
     let mut state: SystemState<Query<(OnDeathHandler, OnInteractionHandler, OnEnterRoomHandler)>> =
         SystemState::new(world);
 
-    // Events to init
+    // Events & their conditions to init
     let mut events: Vec<Arc<Mutex<dyn EventHandler>>> = Vec::new();
+    let mut conditions: Vec<Arc<Mutex<dyn Condition>>> = Vec::new();
 
     // Iterate over queried events, push them to events
     let queries = state.get(world);
@@ -261,55 +844,41 @@ fn init_events(world: &mut World) {
         if let Some(event) = on_death {
             events.push(event.0.clone());
         }
-        if let Some(event) = on_interact {
-            for handler in event.0.values() {
-                events.push(handler.clone());
+        for handlers in on_interact
+            .iter()
+            .flat_map(|event| event.0.values())
+            .chain(on_enter_room.iter().map(|event| &event.0))
+        {
+            for entry in &handlers.0 {
+                events.push(entry.handler.clone());
+                conditions.extend(entry.condition.clone());
             }
         }
-        if let Some(event) = on_enter_room {
-            events.push(event.0.clone());
-        }
+    }
+
+    // Exits can have conditions too
+    for exits in world.query::<&Exits>().iter(world) {
+        conditions.extend(exits.0.values().filter_map(|exit| exit.condition.clone()));
     }
 
     // Iterate over events and init them (must be done this way because otherwise world is borrowed twice)
     for event in events.iter_mut() {
         event.lock().unwrap().init(world);
     }
-}
-
-// ========== CODE CHECKING SYSTEMS ==========
-
-// In development, add some code checks to the app start
-#[cfg(debug_assertions)]
-fn build_debug_system_set() -> SystemSet {
-    SystemSet::new().with_system(active_room_check)
-}
-
-// Make sure an active room was defined, and make sure only 1 was defined
-#[cfg(debug_assertions)]
-fn active_room_check(query: Query<Option<&ActiveRoom>, With<Room>>) {
-    // Count the number of rooms
-    let mut active_rooms: i32 = 0;
-
-    // Iterate through all rooms
-    for active_room in &query {
-        if active_room.is_some() {
-            active_rooms += 1;
-        }
+    for condition in conditions {
+        condition.lock().unwrap().init(world);
     }
 
-    // Make sure there's only 1 room
-    if active_rooms == 0 {
-        panic!(
-            "\x1B[01;31mNo active room was set! \
-                Remember to give one room the `ActiveRoom` component. \
-                This is the room that the player will start in.\x1B[0m"
-        );
-    } else if active_rooms > 1 {
-        panic!(
-            "\x1B[1;31mToo many active rooms were set! \
-                \x1B[0;31mOnly one room can have the `ActiveRoom` component. \
-                This is the room that the player will start in.\x1B[0m"
-        );
+    // Descriptions written by systems need to be initialized too
+    let sources: Vec<_> = world
+        .query::<&DynamicDescription>()
+        .iter(world)
+        .filter_map(|description| match description {
+            DynamicDescription::System(source) => Some(source.clone()),
+            DynamicDescription::Variants { .. } => None,
+        })
+        .collect();
+    for source in sources {
+        source.lock().unwrap().init(world);
     }
 }
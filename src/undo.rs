@@ -0,0 +1,296 @@
+use crate::components::{ActiveRoom, Health, Room};
+use crate::flags::{self, Flags};
+use crate::input_output_manager::IOManager;
+use crate::player::Inventory;
+use crate::systems::move_active_room;
+use crate::validation::descendants_of;
+use bevy::hierarchy::{BuildWorldChildren, Children, DespawnRecursiveExt, Parent};
+use bevy::prelude::{Entity, Resource, With, World};
+use bevy::utils::HashSet;
+use std::collections::VecDeque;
+use std::fmt::{self, Display};
+
+/// The game's state at one point in time, so it can be restored by the `undo` command.
+///
+/// Only adventure entities are part of a snapshot: rooms, everything in them, and everything
+/// the player is carrying. Entities that die are kept out of the world while a snapshot still
+/// has them (see [bury]), so undoing brings them back. Entities despawned by the game itself
+/// can't be, so a snapshot from before that can't be restored (see [Snapshot::is_intact]).
+/// Entities spawned after the snapshot was taken are despawned when it's restored.
+pub struct Snapshot {
+    flags: Flags,
+    inventory: Vec<Entity>,
+    active_room: Entity,
+    autoprompt: bool,
+    health: Vec<(Entity, i32)>,
+    // Every adventure entity, and its parent
+    parents: Vec<(Entity, Option<Entity>)>,
+}
+impl Snapshot {
+    /// Capture the game's current state
+    pub fn take(world: &mut World) -> Self {
+        let active_room = world
+            .query_filtered::<Entity, With<ActiveRoom>>()
+            .single(world);
+        let entities = adventure_entities(world);
+        let health = entities
+            .iter()
+            .filter_map(|entity| Some((*entity, world.get::<Health>(*entity)?.0)))
+            .collect();
+        let parents = entities
+            .iter()
+            .map(|entity| (*entity, world.get::<Parent>(*entity).map(Parent::get)))
+            .collect();
+
+        Self {
+            flags: world.resource::<Flags>().clone(),
+            inventory: world.resource::<Inventory>().0.clone(),
+            active_room,
+            autoprompt: world.resource::<IOManager>().autoprompt,
+            health,
+            parents,
+        }
+    }
+
+    // If this snapshot has `entity` in it
+    fn contains(&self, entity: Entity) -> bool {
+        self.parents.iter().any(|(existed, _)| *existed == entity)
+    }
+
+    /// If every entity in this snapshot still exists, so restoring it won't be a partial rewind
+    pub fn is_intact(&self, world: &World) -> bool {
+        std::iter::once(self.active_room)
+            .chain(self.inventory.iter().copied())
+            .chain(self.health.iter().map(|(entity, _)| *entity))
+            .chain(self.parents.iter().map(|(entity, _)| *entity))
+            .all(|entity| world.get_entity(entity).is_some())
+    }
+
+    /// Put the game back into this snapshot's state.
+    ///
+    /// The active room is changed without firing its [OnEnterRoom](crate::components::OnEnterRoom) event.
+    /// Adventure entities spawned since the snapshot was taken are despawned, and ones that died
+    /// since are brought back. Entities that were despawned since are skipped; check
+    /// [Snapshot::is_intact] first to avoid that.
+    pub fn restore(self, world: &mut World) {
+        // Despawn anything that didn't exist yet
+        let existed: HashSet<Entity> = self.parents.iter().map(|(entity, _)| *entity).collect();
+        let spawned = adventure_entities(world)
+            .into_iter()
+            .filter(|entity| !existed.contains(entity))
+            .collect::<Vec<_>>();
+        for entity in spawned {
+            // Children may have already been despawned with their parent
+            if world.get_entity(entity).is_some() {
+                world.entity_mut(entity).despawn_recursive();
+            }
+        }
+
+        world.resource_mut::<Flags>().replace(self.flags);
+        flags::sync_checkpoints(world);
+        world.resource_mut::<Inventory>().0 = self.inventory;
+
+        // Anything that died since is alive again
+        world
+            .resource_mut::<UndoHistory>()
+            .dead
+            .retain(|entity| !existed.contains(entity));
+        for (entity, health) in self.health {
+            if let Some(mut current) = world.get_mut::<Health>(entity) {
+                current.set_health(health);
+            } else if world.get_entity(entity).is_some() {
+                world.entity_mut(entity).insert(Health(health));
+            }
+        }
+
+        // Re-parent anything that moved
+        for (entity, parent) in self.parents {
+            if world.get_entity(entity).is_none() {
+                continue;
+            }
+            let current = world.get::<Parent>(entity).map(Parent::get);
+            if current == parent {
+                continue;
+            }
+            match parent {
+                Some(parent) if world.get_entity(parent).is_some() => {
+                    world.entity_mut(parent).push_children(&[entity]);
+                }
+                Some(_) => {}
+                None => {
+                    world
+                        .entity_mut(current.unwrap())
+                        .remove_children(&[entity]);
+                }
+            }
+        }
+
+        if world.get_entity(self.active_room).is_some() {
+            move_active_room(world, self.active_room);
+        }
+        world.resource_mut::<IOManager>().autoprompt = self.autoprompt;
+    }
+}
+
+/// The snapshots the `undo` command can go back to, newest last.
+///
+/// A snapshot is taken before each command the player types at the autoprompt.
+/// Once there are more than `depth` snapshots, the oldest ones are dropped.
+#[derive(Resource)]
+pub struct UndoHistory {
+    snapshots: VecDeque<Snapshot>,
+    depth: usize,
+    // Entities that died, but are kept until no snapshot has them (see `bury`)
+    dead: Vec<Entity>,
+}
+impl UndoHistory {
+    /// Make an empty history that keeps up to `depth` snapshots
+    pub fn new(depth: usize) -> Self {
+        Self {
+            snapshots: VecDeque::with_capacity(depth),
+            depth,
+            dead: Vec::new(),
+        }
+    }
+    /// Add a snapshot, dropping the oldest one if the history is full
+    pub fn push(&mut self, snapshot: Snapshot) {
+        if self.depth == 0 {
+            return;
+        }
+        if self.snapshots.len() == self.depth {
+            self.snapshots.pop_front();
+        }
+        self.snapshots.push_back(snapshot);
+    }
+    /// Take the newest snapshot out of the history
+    pub fn pop(&mut self) -> Option<Snapshot> {
+        self.snapshots.pop_back()
+    }
+    /// Forget every snapshot
+    pub fn clear(&mut self) {
+        self.snapshots.clear();
+    }
+    /// How many snapshots can currently be undone
+    pub fn len(&self) -> usize {
+        self.snapshots.len()
+    }
+    /// If there's nothing to undo
+    pub fn is_empty(&self) -> bool {
+        self.snapshots.is_empty()
+    }
+}
+
+/// Take a snapshot and add it to the [UndoHistory]
+pub fn record(world: &mut World) {
+    if world.resource::<UndoHistory>().depth == 0 {
+        return;
+    }
+    let snapshot = Snapshot::take(world);
+    world.resource_mut::<UndoHistory>().push(snapshot);
+    // The oldest snapshot may have been dropped to make room
+    despawn_forgotten(world);
+}
+
+/// Take an entity that died out of the game.
+///
+/// If undo is on, the entity is only taken out of its room (or the player's inventory) and
+/// loses its [Health], so undoing can put it back. It's despawned once no snapshot in the
+/// [UndoHistory] has it any more. Games can bury entities themselves, instead of despawning
+/// them, so undoing can bring those back too.
+pub fn bury(world: &mut World, entity: Entity) {
+    if world.resource::<UndoHistory>().depth == 0 {
+        world.despawn(entity);
+        return;
+    }
+
+    if let Some(parent) = world.get::<Parent>(entity).map(Parent::get) {
+        world.entity_mut(parent).remove_children(&[entity]);
+    }
+    world.entity_mut(entity).remove::<Health>();
+    world
+        .resource_mut::<Inventory>()
+        .0
+        .retain(|item| *item != entity);
+    world.resource_mut::<UndoHistory>().dead.push(entity);
+}
+
+// Despawn the dead entities that no snapshot can bring back any more
+fn despawn_forgotten(world: &mut World) {
+    let mut history = world.resource_mut::<UndoHistory>();
+    let (forgotten, kept) = std::mem::take(&mut history.dead)
+        .into_iter()
+        .partition::<Vec<_>, _>(|entity| {
+            !history
+                .snapshots
+                .iter()
+                .any(|snapshot| snapshot.contains(*entity))
+        });
+    history.dead = kept;
+
+    for entity in forgotten {
+        // Restarting the game may have despawned it already
+        if world.get_entity(entity).is_some() {
+            world.entity_mut(entity).despawn_recursive();
+        }
+    }
+}
+
+// Every adventure entity: the rooms, everything in them, and everything the player is carrying
+fn adventure_entities(world: &mut World) -> Vec<Entity> {
+    let rooms = world
+        .query_filtered::<Entity, With<Room>>()
+        .iter(world)
+        .collect::<Vec<_>>();
+    let mut children = world.query::<&Children>();
+
+    let mut entities = Vec::new();
+    for room in rooms {
+        entities.push(room);
+        entities.extend(descendants_of(world, &mut children, room));
+    }
+    for item in &world.resource::<Inventory>().0 {
+        if !entities.contains(item) {
+            entities.push(*item);
+        }
+    }
+    entities
+}
+
+/// Why the `undo` command couldn't go back
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum UndoError {
+    /// The [UndoHistory] is empty
+    NothingToUndo,
+    /// Something was despawned since the snapshot was taken, so it can't be put back
+    Despawned,
+}
+impl Display for UndoError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::NothingToUndo => write!(f, "There's nothing to undo."),
+            Self::Despawned => write!(f, "That can't be undone."),
+        }
+    }
+}
+impl std::error::Error for UndoError {}
+
+/// Restore the newest snapshot in the [UndoHistory].
+///
+/// If something in the snapshot has been despawned since, nothing is restored. Every older
+/// snapshot is from before the despawn too, so the history is cleared.
+pub fn undo(world: &mut World) -> Result<(), UndoError> {
+    let snapshot = world
+        .resource_mut::<UndoHistory>()
+        .pop()
+        .ok_or(UndoError::NothingToUndo)?;
+
+    let result = if snapshot.is_intact(world) {
+        snapshot.restore(world);
+        Ok(())
+    } else {
+        world.resource_mut::<UndoHistory>().clear();
+        Err(UndoError::Despawned)
+    };
+    despawn_forgotten(world);
+    result
+}
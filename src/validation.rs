@@ -0,0 +1,483 @@
+use crate::components::{ActiveRoom, Aliases, Exits, Name, OnInteract, Room};
+use crate::input_output_manager::Vocabulary;
+use crate::parser::is_stop_word;
+use crate::rooms::RoomId;
+use bevy::prelude::{Children, Entity, QueryState, Resource, With, Without, World};
+use bevy::utils::{HashMap, HashSet};
+use std::fmt::{self, Display};
+
+/// How serious a [Problem] is
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Severity {
+    /// Probably a mistake, but the game can still run
+    Warning,
+    /// The game will break - for example, the player can't start, or can't go somewhere
+    Error,
+}
+
+/// Something wrong with the game's world, found by [validate_world]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Problem {
+    /// How serious the problem is
+    pub severity: Severity,
+    /// What's wrong
+    pub message: String,
+}
+impl Display for Problem {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.severity {
+            Severity::Warning => write!(f, "warning: {}", self.message),
+            Severity::Error => write!(f, "error: {}", self.message),
+        }
+    }
+}
+
+/// Every [Problem] found in the game's world at startup.
+///
+/// This is inserted as a resource after the world is validated, so it can be checked in tests.
+#[derive(Resource, Debug, Clone, Default)]
+pub struct ValidationReport {
+    /// The problems, in the order they were found
+    pub problems: Vec<Problem>,
+}
+impl ValidationReport {
+    /// If nothing is wrong
+    pub fn is_empty(&self) -> bool {
+        self.problems.is_empty()
+    }
+    /// If any problem is an [Severity::Error]
+    pub fn has_errors(&self) -> bool {
+        self.errors().next().is_some()
+    }
+    /// Every [Severity::Error]
+    pub fn errors(&self) -> impl Iterator<Item = &Problem> {
+        self.problems
+            .iter()
+            .filter(|problem| problem.severity == Severity::Error)
+    }
+    /// Every [Severity::Warning]
+    pub fn warnings(&self) -> impl Iterator<Item = &Problem> {
+        self.problems
+            .iter()
+            .filter(|problem| problem.severity == Severity::Warning)
+    }
+
+    fn warn(&mut self, message: String) {
+        self.problems.push(Problem {
+            severity: Severity::Warning,
+            message,
+        });
+    }
+    fn error(&mut self, message: String) {
+        self.problems.push(Problem {
+            severity: Severity::Error,
+            message,
+        });
+    }
+}
+
+/// Check the game's world for mistakes.
+///
+/// Errors:
+/// - There isn't exactly one [ActiveRoom]
+/// - Two rooms have the same name
+/// - An exit leads to a room that doesn't exist
+/// - An entity in a room has [OnInteract], but no [Name], so the player can't interact with it
+///
+/// Warnings:
+/// - Two entities in the same room share a name or alias, so the player has to be asked which one they mean
+/// - A room can't be reached from the starting room through [Exits]
+///   (only checked if the game uses exits at all, since rooms can also be reached with `set_room`)
+/// - An [OnInteract] has no handlers
+pub fn validate_world(world: &mut World) -> ValidationReport {
+    let mut report = ValidationReport::default();
+
+    check_active_room(world, &mut report);
+    check_room_names(world, &mut report);
+    check_exits(world, &mut report);
+    check_entity_names(world, &mut report);
+    check_interactions(world, &mut report);
+
+    report
+}
+
+/// Validate the world at startup, print any problems, and panic if there are errors.
+/// This runs in debug builds, or in any build if
+/// [AdventurePlugin::validate_world](crate::plugin::AdventurePlugin::validate_world) is set.
+pub(crate) fn validation_system(world: &mut World) {
+    let report = validate_world(world);
+
+    // MinimalPlugins doesn't log anything, so print problems straight to stderr,
+    //  where the game's author will see them
+    for problem in &report.problems {
+        eprintln!("{}", problem);
+    }
+    let errors: Vec<String> = report.errors().map(ToString::to_string).collect();
+    world.insert_resource(report);
+
+    if !errors.is_empty() {
+        panic!(
+            "The game's world has {} error(s):\n{}",
+            errors.len(),
+            errors.join("\n")
+        );
+    }
+}
+
+// There must be exactly one room the player starts in
+fn check_active_room(world: &mut World, report: &mut ValidationReport) {
+    let active_rooms = world
+        .query_filtered::<(), (With<Room>, With<ActiveRoom>)>()
+        .iter(world)
+        .count();
+
+    if active_rooms == 0 {
+        report.error(
+            "No active room was set! Remember to give one room the `ActiveRoom` component. \
+                This is the room that the player will start in."
+                .to_owned(),
+        );
+    } else if active_rooms > 1 {
+        report.error(format!(
+            "{} active rooms were set! Only one room can have the `ActiveRoom` component. \
+                This is the room that the player will start in.",
+            active_rooms
+        ));
+    }
+}
+
+// Room names are room IDs, so they have to be unique
+fn check_room_names(world: &mut World, report: &mut ValidationReport) {
+    let mut counts: HashMap<&str, usize> = HashMap::new();
+    for room in world.query::<&Room>().iter(world) {
+        *counts.entry(room.name).or_default() += 1;
+    }
+
+    let mut duplicates: Vec<_> = counts.into_iter().filter(|(_, count)| *count > 1).collect();
+    duplicates.sort();
+    for (name, count) in duplicates {
+        report.error(format!("{} rooms are named `{}`", count, name));
+    }
+}
+
+// Every exit should lead somewhere, and every room should be reachable
+fn check_exits(world: &mut World, report: &mut ValidationReport) {
+    let mut rooms: Vec<(RoomId, Option<&Exits>, bool)> = world
+        .query::<(&Room, Option<&Exits>, Option<&ActiveRoom>)>()
+        .iter(world)
+        .map(|(room, exits, active)| (room.id(), exits, active.is_some()))
+        .collect();
+    rooms.sort_by_key(|(id, _, _)| id.name());
+    let ids: HashSet<RoomId> = rooms.iter().map(|(id, _, _)| *id).collect();
+
+    for (room, exits) in rooms
+        .iter()
+        .filter_map(|(id, exits, _)| exits.map(|exits| (id, exits)))
+    {
+        let mut exits: Vec<_> = exits.0.iter().collect();
+        exits.sort_by_key(|(direction, _)| direction.name());
+        for (direction, exit) in exits {
+            if !ids.contains(&exit.target) {
+                report.error(format!(
+                    "The `{}` exit in room `{}` leads to `{}`, which does not exist",
+                    direction.name(),
+                    room,
+                    exit.target
+                ));
+            }
+        }
+    }
+
+    // Rooms might only be reached with set_room, so only check games that use exits
+    if rooms.iter().all(|(_, exits, _)| exits.is_none()) {
+        return;
+    }
+    let start = match rooms.iter().find(|(_, _, active)| *active) {
+        Some((start, _, _)) => *start,
+        None => return,
+    };
+
+    // Walk every exit from the starting room
+    let exits: HashMap<RoomId, &Exits> = rooms
+        .iter()
+        .filter_map(|(id, exits, _)| exits.map(|exits| (*id, exits)))
+        .collect();
+    let mut reached = HashSet::new();
+    let mut queue = vec![start];
+    while let Some(room) = queue.pop() {
+        if !reached.insert(room) {
+            continue;
+        }
+        if let Some(exits) = exits.get(&room) {
+            queue.extend(exits.0.values().map(|exit| exit.target));
+        }
+    }
+
+    for (room, _, _) in &rooms {
+        if !reached.contains(room) {
+            report.warn(format!(
+                "Room `{}` can't be reached from the starting room `{}` through any exits",
+                room, start
+            ));
+        }
+    }
+}
+
+// The player picks entities by name, so names in one room shouldn't overlap
+fn check_entity_names(world: &mut World, report: &mut ValidationReport) {
+    let mut rooms: Vec<(Entity, &str)> = world
+        .query::<(Entity, &Room)>()
+        .iter(world)
+        .map(|(entity, room)| (entity, room.name))
+        .collect();
+    rooms.sort_by_key(|(_, name)| *name);
+
+    let mut children = world.query::<&Children>();
+    let mut names = world.query::<(&Name, Option<&Aliases>)>();
+
+    let vocabulary = world
+        .get_resource::<Vocabulary>()
+        .cloned()
+        .unwrap_or_default();
+
+    for (room, room_name) in rooms {
+        let room_vocabulary = world.get::<Vocabulary>(room).cloned();
+        let vocabularies: Vec<&Vocabulary> = room_vocabulary
+            .iter()
+            .chain(std::iter::once(&vocabulary))
+            .collect();
+
+        // Every word the player could use for something in this room, and what it refers to
+        let mut words: HashMap<String, Vec<&str>> = HashMap::new();
+        for entity in descendants_of(world, &mut children, room) {
+            if let Ok((name, aliases)) = names.get(world, entity) {
+                // Names are matched ignoring case, spacing & stop words
+                let mut own_words: HashSet<String> = HashSet::new();
+                for word in std::iter::once(name.0)
+                    .chain(aliases.iter().flat_map(|aliases| aliases.0.iter().copied()))
+                {
+                    own_words.insert(
+                        word.split_whitespace()
+                            .filter(|word| !is_stop_word(&vocabularies, word))
+                            .collect::<Vec<_>>()
+                            .join(" ")
+                            .to_lowercase(),
+                    );
+                }
+                for word in own_words {
+                    words.entry(word).or_default().push(name.0);
+                }
+            }
+        }
+
+        let mut collisions: Vec<_> = words
+            .into_iter()
+            .filter(|(_, entities)| entities.len() > 1)
+            .collect();
+        collisions.sort();
+        for (word, entities) in collisions {
+            report.warn(format!(
+                "In room `{}`, `{}` could refer to any of: {}",
+                room_name,
+                word,
+                entities.join(", ")
+            ));
+        }
+    }
+}
+
+// Every entity in a room (not just direct children)
+pub(crate) fn descendants_of(
+    world: &World,
+    children: &mut QueryState<&Children>,
+    room: Entity,
+) -> Vec<Entity> {
+    let mut found = Vec::new();
+    let mut queue = vec![room];
+    while let Some(entity) = queue.pop() {
+        if let Ok(kids) = children.get(world, entity) {
+            found.extend(kids.iter().copied());
+            queue.extend(kids.iter().copied());
+        }
+    }
+    found
+}
+
+// Interactions need a name to be triggered, and at least one handler to do anything
+fn check_interactions(world: &mut World, report: &mut ValidationReport) {
+    let unnamed = world
+        .query_filtered::<(), (With<OnInteract>, Without<Name>, Without<Room>)>()
+        .iter(world)
+        .count();
+    if unnamed > 0 {
+        report.error(format!(
+            "{} entities have an `OnInteract` but no `Name`, so the player can't interact with them",
+            unnamed
+        ));
+    }
+
+    let mut empty: Vec<String> = world
+        .query::<(&OnInteract, Option<&Name>, Option<&Room>)>()
+        .iter(world)
+        .filter(|(on_interact, _, _)| on_interact.0.is_empty())
+        .map(|(_, name, room)| match (name, room) {
+            (Some(name), _) => format!("`{}`", name.0),
+            (None, Some(room)) => format!("room `{}`", room.name),
+            (None, None) => "an unnamed entity".to_owned(),
+        })
+        .collect();
+    empty.sort();
+    for entity in empty {
+        report.warn(format!(
+            "The `OnInteract` on {} has no handlers, so interacting with it does nothing",
+            entity
+        ));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::adventure_commands::AdventureEntityCommands;
+    use crate::components::Direction;
+    use crate::input_output_manager::WordType;
+    use bevy::ecs::system::SystemState;
+    use bevy::prelude::{BuildChildren, Commands};
+
+    fn ring() {}
+
+    // Validate the world `build` spawns
+    fn validate(build: impl FnOnce(Commands)) -> ValidationReport {
+        let mut world = World::new();
+        world.init_resource::<Vocabulary>();
+        let mut state: SystemState<Commands> = SystemState::new(&mut world);
+        build(state.get_mut(&mut world));
+        state.apply(&mut world);
+        validate_world(&mut world)
+    }
+
+    fn messages<'a>(problems: impl Iterator<Item = &'a Problem>) -> Vec<&'a str> {
+        problems.map(|problem| problem.message.as_str()).collect()
+    }
+
+    #[test]
+    fn unreachable_rooms() {
+        let report = validate(|mut commands| {
+            commands.spawn((
+                Room {
+                    name: "Yard",
+                    description: None,
+                },
+                Exits::new().with(Direction::North, RoomId::new("Field")),
+                ActiveRoom,
+            ));
+            commands.spawn(Room {
+                name: "Field",
+                description: None,
+            });
+            commands.spawn(Room {
+                name: "Attic",
+                description: None,
+            });
+        });
+
+        assert!(!report.has_errors());
+        assert_eq!(
+            messages(report.warnings()),
+            vec!["Room `Attic` can't be reached from the starting room `Yard` through any exits"]
+        );
+    }
+
+    #[test]
+    fn games_without_exits_arent_checked_for_unreachable_rooms() {
+        let report = validate(|mut commands| {
+            commands.spawn((
+                Room {
+                    name: "Yard",
+                    description: None,
+                },
+                ActiveRoom,
+            ));
+            commands.spawn(Room {
+                name: "Attic",
+                description: None,
+            });
+        });
+
+        assert!(report.is_empty());
+    }
+
+    #[test]
+    fn shared_names_and_aliases() {
+        let report = validate(|mut commands| {
+            let son = commands.spawn(Name("son")).id();
+            let daughter = commands
+                .spawn((Name("daughter"), Aliases(vec!["The Son"])))
+                .id();
+            commands
+                .spawn((
+                    Room {
+                        name: "Yard",
+                        description: None,
+                    },
+                    ActiveRoom,
+                ))
+                .push_children(&[son, daughter]);
+        });
+
+        assert!(!report.has_errors());
+        let warnings = messages(report.warnings());
+        assert_eq!(warnings.len(), 1);
+        // Stop words & case don't matter
+        assert!(warnings[0].starts_with("In room `Yard`, `son` could refer to any of: "));
+        assert!(warnings[0].contains("son") && warnings[0].contains("daughter"));
+    }
+
+    #[test]
+    fn empty_interactions() {
+        let report = validate(|mut commands| {
+            let statue = commands
+                .spawn((Name("statue"), OnInteract(HashMap::new())))
+                .id();
+            commands
+                .spawn((
+                    Room {
+                        name: "Yard",
+                        description: None,
+                    },
+                    ActiveRoom,
+                ))
+                .add_child(statue);
+        });
+
+        assert!(!report.has_errors());
+        assert_eq!(
+            messages(report.warnings()),
+            vec![
+                "The `OnInteract` on `statue` has no handlers, so interacting with it does nothing"
+            ]
+        );
+    }
+
+    #[test]
+    fn unnamed_interactions() {
+        let report = validate(|mut commands| {
+            let bell = commands.spawn_empty().on_interact(WordType::Use, ring).id();
+            commands
+                .spawn((
+                    Room {
+                        name: "Yard",
+                        description: None,
+                    },
+                    ActiveRoom,
+                ))
+                .add_child(bell);
+        });
+
+        assert_eq!(
+            messages(report.errors()),
+            vec!["1 entities have an `OnInteract` but no `Name`, so the player can't interact with them"]
+        );
+        assert!(report.warnings().next().is_none());
+    }
+}
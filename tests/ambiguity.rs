@@ -0,0 +1,60 @@
+use bevy::prelude::*;
+use bevy_text_adventure::components::Name;
+use bevy_text_adventure::prelude::*;
+
+mod common;
+
+use common::spawn_start_room;
+
+fn talk_to_sentry(iomgr: Res<IOManager>) {
+    iomgr.println("The sentry salutes.");
+}
+
+fn talk_to_sleepy_guard(iomgr: Res<IOManager>) {
+    iomgr.println("The guard snores.");
+}
+
+fn build(mut commands: Commands) {
+    let sentry = commands
+        .spawn((Name("guard"), Aliases(vec!["sentry"])))
+        .on_interact(WordType::Talk, talk_to_sentry)
+        .id();
+    let sleepy_guard = commands
+        .spawn((Name("guard"), Description("A sleepy guard.".to_owned())))
+        .on_interact(WordType::Talk, talk_to_sleepy_guard)
+        .id();
+    let coins = [
+        commands.spawn((Name("coin"), Portable)).id(),
+        commands.spawn((Name("coin"), Portable)).id(),
+    ];
+
+    spawn_start_room(
+        &mut commands,
+        Room {
+            name: "Gate",
+            description: None,
+        },
+        &[sentry, sleepy_guard, coins[0], coins[1]],
+    );
+}
+
+#[test]
+fn entities_with_the_same_name_are_told_apart() {
+    let transcript = AdventureHarness::new(["talk to guard", "2"])
+        .add_startup_system(build)
+        .run();
+
+    assert!(transcript.contains("1. sentry\n2. guard - A sleepy guard."));
+    assert!(transcript.contains("The guard snores."));
+}
+
+#[test]
+fn carried_items_are_told_apart() {
+    let transcript = AdventureHarness::new(["take coin", "1", "look at coin", "2"])
+        .add_startup_system(build)
+        .run();
+
+    // Neither coin is carried the first time
+    assert!(transcript.contains("1. coin\n2. coin\n"));
+    assert!(transcript.contains("1. coin\n2. coin (carried)"));
+}
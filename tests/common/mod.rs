@@ -0,0 +1,82 @@
+// Each test binary only uses some of these
+#![allow(dead_code)]
+
+use bevy::ecs::system::EntityCommands;
+use bevy::prelude::*;
+use bevy_text_adventure::prelude::*;
+
+// ========== ROOMS ==========
+
+/// Says which room the player is in, then prompts them
+pub fn enter(mut iomgr: ResMut<IOManager>, room: Query<&Room, With<ActiveRoom>>) {
+    iomgr.println(format!(
+        "You're in the {}.",
+        room.single().name.to_lowercase()
+    ));
+    iomgr.autoprompt();
+}
+
+/// Spawns `room` holding `things`, which says its name when the player enters it
+pub fn spawn_room<'w, 's, 'a>(
+    commands: &'a mut Commands<'w, 's>,
+    room: Room,
+    things: &[Entity],
+) -> EntityCommands<'w, 's, 'a> {
+    let mut entity = commands.spawn(room);
+    entity.on_enter_room(enter).push_children(things);
+    entity
+}
+
+/// The same as [spawn_room], but the game starts in it
+pub fn spawn_start_room<'w, 's, 'a>(
+    commands: &'a mut Commands<'w, 's>,
+    room: Room,
+    things: &[Entity],
+) -> EntityCommands<'w, 's, 'a> {
+    let mut entity = spawn_room(commands, room, things);
+    entity.insert(ActiveRoom);
+    entity
+}
+
+// ========== INVASION ==========
+
+// A small version of the opening of the amstud example's invasion chapter
+
+fn invasion(mut iomgr: ResMut<IOManager>) {
+    iomgr.println("You wake up to the sound of gunshots. You should go get your kids!");
+    iomgr.autoprompt();
+}
+
+fn take_kids(iomgr: Res<IOManager>, mut flags: ResMut<Flags>) {
+    iomgr.println("You grab your kids.");
+    flags.set("kids", true);
+}
+
+fn leave(iomgr: Res<IOManager>, mut commands: Commands) {
+    iomgr.println("Do you leave the house?");
+    if iomgr.yes_no_prompt() {
+        iomgr.println("Goodbye!");
+        commands.quit_game();
+    }
+}
+
+pub fn build(mut commands: Commands) {
+    let kids = commands
+        .spawn((
+            Name("kids"),
+            Aliases(vec!["children"]),
+            Refusal("Your kids are already with you."),
+        ))
+        .on_interact_if(WordType::Take, NotFlag("kids"), take_kids)
+        .id();
+
+    commands
+        .spawn(Room {
+            name: "Invasion",
+            description: None,
+        })
+        .on_enter_room(invasion)
+        .on_interact(WordType::Move, leave)
+        .insert(ActiveRoom)
+        .add_child(kids);
+}
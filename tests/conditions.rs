@@ -0,0 +1,100 @@
+use bevy::prelude::*;
+use bevy_text_adventure::components::Name;
+use bevy_text_adventure::prelude::*;
+
+mod common;
+
+use common::spawn_start_room;
+
+fn first_visit(mut iomgr: ResMut<IOManager>, mut flags: ResMut<Flags>) {
+    iomgr.println("You enter the hall for the first time.");
+    flags.set("visited", true);
+    iomgr.autoprompt();
+}
+
+fn welcome_back(mut iomgr: ResMut<IOManager>) {
+    iomgr.println("Welcome back to the hall.");
+    iomgr.autoprompt();
+}
+
+fn take_key(iomgr: Res<IOManager>, mut flags: ResMut<Flags>) {
+    iomgr.println("You take the key.");
+    flags.set("key", true);
+}
+
+fn open_chest(iomgr: Res<IOManager>) {
+    iomgr.println("The chest opens.");
+}
+
+fn poke_chest(iomgr: Res<IOManager>) {
+    iomgr.println("You poke the chest.");
+}
+
+fn has_key(flags: Res<Flags>) -> bool {
+    flags.is_set("key")
+}
+
+fn build(mut commands: Commands) {
+    let key = commands
+        .spawn(Name("key"))
+        .on_interact(WordType::Take, take_key)
+        .id();
+    let chest = commands
+        .spawn(Name("chest"))
+        .on_interact_if(WordType::Open, has_key, open_chest)
+        .on_interact(WordType::Any, poke_chest)
+        .id();
+
+    spawn_start_room(
+        &mut commands,
+        Room {
+            name: "Yard",
+            description: None,
+        },
+        &[key, chest],
+    )
+    .insert(Exits::new().with(Direction::North, RoomId::new("Hall")));
+    commands
+        .spawn((
+            Room {
+                name: "Hall",
+                description: None,
+            },
+            Exits::new().with(Direction::South, RoomId::new("Yard")),
+        ))
+        .on_enter_room_if(Flag("visited"), welcome_back)
+        .on_enter_room(first_visit);
+}
+
+#[test]
+fn failed_conditions_fall_through_to_any() {
+    let transcript = AdventureHarness::new(["open chest", "take key", "open chest"])
+        .add_startup_system(build)
+        .run();
+
+    let output = transcript.output();
+    let poke = output.find("You poke the chest.").unwrap();
+    let opens = output.find("The chest opens.").unwrap();
+    assert!(poke < opens);
+    assert_eq!(output.matches("You poke the chest.").count(), 1);
+}
+
+#[test]
+fn enter_room_conditions() {
+    let transcript = AdventureHarness::new(["north", "south", "north"])
+        .add_startup_system(build)
+        .run();
+
+    let output = transcript.output();
+    assert_eq!(
+        output
+            .matches("You enter the hall for the first time.")
+            .count(),
+        1
+    );
+    assert_eq!(output.matches("Welcome back to the hall.").count(), 1);
+    assert!(
+        output.find("You enter the hall for the first time.")
+            < output.find("Welcome back to the hall.")
+    );
+}
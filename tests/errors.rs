@@ -0,0 +1,53 @@
+use bevy::prelude::*;
+use bevy_text_adventure::components::Name;
+use bevy_text_adventure::prelude::*;
+
+mod common;
+
+use common::spawn_start_room;
+
+fn open_door(mut commands: Commands) {
+    // There's no hall to go to
+    commands.try_set_room(RoomId::new("Hall"));
+}
+
+fn report(mut errors: EventReader<AdventureError>, iomgr: Res<IOManager>) {
+    for err in errors.iter() {
+        match err {
+            AdventureError::RoomNotFound(room) => {
+                iomgr.println(format!("There's no room called {}.", room))
+            }
+        }
+    }
+}
+
+fn build(mut commands: Commands) {
+    let door = commands
+        .spawn(Name("door"))
+        .on_interact(WordType::Open, open_door)
+        .id();
+
+    spawn_start_room(
+        &mut commands,
+        Room {
+            name: "Cell",
+            description: Some("A cold, damp cell."),
+        },
+        &[door],
+    );
+}
+
+#[test]
+fn missing_rooms_run_the_error_handler() {
+    let mut harness = AdventureHarness::new(["open door", "look"]).add_startup_system(build);
+    harness.app().set_error_handler(report);
+    let transcript = harness.run();
+
+    assert!(transcript.contains("There's no room called Hall."));
+    // The player is still in the cell, and didn't enter it again
+    assert!(transcript.contains("A cold, damp cell.\nYou see: door."));
+    assert_eq!(
+        transcript.output().matches("You're in the cell.").count(),
+        1
+    );
+}
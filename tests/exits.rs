@@ -0,0 +1,127 @@
+use bevy::prelude::*;
+use bevy_text_adventure::prelude::*;
+
+mod common;
+
+use common::{spawn_room, spawn_start_room};
+
+const YARD: RoomId = RoomId::new("Yard");
+const FIELD: RoomId = RoomId::new("Field");
+const HOUSE: RoomId = RoomId::new("House");
+const SHED: RoomId = RoomId::new("Shed");
+
+fn take_key(iomgr: Res<IOManager>, mut flags: ResMut<Flags>) {
+    iomgr.println("You take the key.");
+    flags.set("key", true);
+}
+
+fn shed_unlocked(flags: Res<Flags>) -> bool {
+    flags.int("visits") > 0
+}
+
+fn count_visit(mut flags: ResMut<Flags>) {
+    flags.incr("visits", 1);
+}
+
+fn build(mut commands: Commands) {
+    let key = commands
+        .spawn(Name("key"))
+        .on_interact(WordType::Take, take_key)
+        .id();
+
+    spawn_start_room(
+        &mut commands,
+        Room {
+            name: YARD.name(),
+            description: None,
+        },
+        &[key],
+    )
+    .insert(
+        Exits::new()
+            .with(Direction::North, FIELD)
+            .with(
+                Direction::Custom("front door"),
+                Exit::to(HOUSE)
+                    .only_if(Flag("key"))
+                    .blocked_message("The front door is locked."),
+            )
+            .with(
+                Direction::In,
+                Exit::to(SHED)
+                    .only_if(shed_unlocked)
+                    .blocked_message("The shed is locked."),
+            ),
+    );
+    spawn_room(
+        &mut commands,
+        Room {
+            name: FIELD.name(),
+            description: None,
+        },
+        &[],
+    )
+    .insert(Exits::new().with(Direction::South, YARD))
+    .on_interact(WordType::Look, count_visit);
+    spawn_room(
+        &mut commands,
+        Room {
+            name: HOUSE.name(),
+            description: None,
+        },
+        &[],
+    );
+    spawn_room(
+        &mut commands,
+        Room {
+            name: SHED.name(),
+            description: None,
+        },
+        &[],
+    );
+}
+
+#[test]
+fn go_to_the_front_door() {
+    let transcript =
+        AdventureHarness::new(["go to the front door", "take key", "go to the front door"])
+            .add_startup_system(build)
+            .run();
+
+    assert!(transcript.contains("The front door is locked."));
+    assert!(transcript.contains("You're in the house."));
+}
+
+#[test]
+fn prepositions_and_stop_words_before_the_exit_are_skipped() {
+    let transcript = AdventureHarness::new(["take key", "run into the front door"])
+        .add_startup_system(build)
+        .run();
+
+    assert!(transcript.contains("You're in the house."));
+}
+
+#[test]
+fn compass_directions() {
+    let transcript = AdventureHarness::new(["n", "go to the south", "go east"])
+        .add_startup_system(build)
+        .run();
+
+    assert!(transcript.contains("You're in the field."));
+    assert_eq!(
+        transcript.output().matches("You're in the yard.").count(),
+        2
+    );
+    assert!(transcript.contains("You can't go that way."));
+}
+
+#[test]
+fn system_conditions() {
+    // A lone "in" is a direction, not a preposition
+    let transcript = AdventureHarness::new(["in", "north", "look", "south", "go in"])
+        .add_startup_system(build)
+        .run();
+
+    assert!(transcript.contains("The shed is locked."));
+    assert!(transcript.contains("You're in the shed."));
+}
@@ -0,0 +1,91 @@
+use bevy::prelude::*;
+use bevy_text_adventure::harness::{InputExhausted, TranscriptEntry};
+use bevy_text_adventure::prelude::*;
+use std::panic;
+
+mod common;
+
+use common::build;
+
+#[test]
+fn take_kids_then_quit() {
+    let transcript = AdventureHarness::new(["take kids", "take children", "leave", "y", "look"])
+        .add_startup_system(build)
+        .run();
+
+    assert!(transcript.contains("You wake up to the sound of gunshots."));
+    assert!(transcript.contains("You grab your kids."));
+    assert!(transcript.contains("Your kids are already with you."));
+    assert!(transcript.contains("Goodbye!"));
+    // The game quit before it asked for the last input
+    assert_eq!(transcript.unused_inputs, vec!["look"]);
+}
+
+#[test]
+fn output_is_recorded_between_inputs() {
+    let transcript = AdventureHarness::new(["take kids"])
+        .add_startup_system(build)
+        .run();
+
+    // The opening, then the input, then the response
+    match &transcript.entries[..] {
+        [TranscriptEntry::Output(opening), TranscriptEntry::Input(input), TranscriptEntry::Output(response)] =>
+        {
+            assert!(opening.contains("You wake up"));
+            assert_eq!(input, "take kids");
+            assert!(response.starts_with("You grab your kids."));
+        }
+        entries => panic!("Unexpected transcript: {:?}", entries),
+    }
+}
+
+#[test]
+fn running_out_of_input_stops_the_game() {
+    // The game keeps prompting, so the harness has to stop it once the script runs out
+    let transcript = AdventureHarness::new(["take kids"])
+        .add_startup_system(build)
+        .run();
+
+    assert!(transcript.contains("You grab your kids."));
+    assert!(transcript.unused_inputs.is_empty());
+    // The last thing the game did was prompt for the next command
+    assert!(matches!(
+        transcript.entries.last(),
+        Some(TranscriptEntry::Output(_))
+    ));
+}
+
+#[test]
+fn scripted_io_unwinds_with_input_exhausted() {
+    let io = ScriptedIo::new(["only line"]);
+    assert_eq!(io.read_line(), "only line\n");
+
+    let payload = panic::catch_unwind(|| io.read_line()).unwrap_err();
+    assert!(payload.is::<InputExhausted>());
+
+    // The script isn't poisoned, so it can still be read after unwinding
+    io.push_input("another line");
+    assert_eq!(io.read_line(), "another line\n");
+    assert!(io.transcript().unused_inputs.is_empty());
+}
+
+#[test]
+#[should_panic(expected = "not input exhaustion")]
+fn other_panics_are_passed_through() {
+    fn broken() {
+        panic!("not input exhaustion");
+    }
+    fn build_broken(mut commands: Commands) {
+        commands
+            .spawn(Room {
+                name: "Broken",
+                description: None,
+            })
+            .on_enter_room(broken)
+            .insert(ActiveRoom);
+    }
+
+    AdventureHarness::new(["look"])
+        .add_startup_system(build_broken)
+        .run();
+}
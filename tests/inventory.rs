@@ -0,0 +1,95 @@
+use bevy::prelude::*;
+use bevy_text_adventure::components::Name;
+use bevy_text_adventure::prelude::*;
+
+mod common;
+
+use common::{spawn_room, spawn_start_room};
+
+fn light_lantern(iomgr: Res<IOManager>) {
+    iomgr.println("The lantern flickers on.");
+}
+
+fn build(mut commands: Commands) {
+    let lantern = commands
+        .spawn((
+            Name("lantern"),
+            Portable,
+            Description("A brass lantern.".to_owned()),
+        ))
+        .on_interact(WordType::Use, light_lantern)
+        .id();
+    let barrel = commands.spawn(Name("barrel")).id();
+
+    spawn_start_room(
+        &mut commands,
+        Room {
+            name: "Cellar",
+            description: None,
+        },
+        &[lantern, barrel],
+    )
+    .insert(Exits::new().with(Direction::Up, RoomId::new("Kitchen")));
+    spawn_room(
+        &mut commands,
+        Room {
+            name: "Kitchen",
+            description: None,
+        },
+        &[],
+    )
+    .insert(Exits::new().with(Direction::Down, RoomId::new("Cellar")));
+}
+
+#[test]
+fn take_items() {
+    let transcript = AdventureHarness::new(["take lantern", "take lantern", "look"])
+        .add_startup_system(build)
+        .run();
+
+    assert!(transcript.contains("You take the lantern."));
+    assert!(transcript.contains("You already have the lantern."));
+    // It isn't in the room any more
+    assert!(transcript.contains("You see: barrel."));
+}
+
+#[test]
+fn drop_items_in_the_active_room() {
+    let transcript =
+        AdventureHarness::new(["drop lantern", "take lantern", "up", "drop lantern", "look"])
+            .add_startup_system(build)
+            .run();
+
+    assert!(transcript.contains("You aren't carrying the lantern."));
+    assert!(transcript.contains("You drop the lantern."));
+    assert!(transcript.contains("You see: lantern."));
+}
+
+#[test]
+fn list_the_inventory() {
+    let transcript = AdventureHarness::new(["i", "take lantern", "inventory"])
+        .add_startup_system(build)
+        .run();
+
+    assert!(transcript.contains("You aren't carrying anything."));
+    assert!(transcript.contains("You're carrying: lantern."));
+}
+
+#[test]
+fn carried_items_can_be_used_in_other_rooms() {
+    let transcript = AdventureHarness::new([
+        "take lantern",
+        "up",
+        "look at lantern",
+        "use lantern",
+        "look at barrel",
+    ])
+    .add_startup_system(build)
+    .run();
+
+    assert!(transcript.contains("You're in the kitchen."));
+    assert!(transcript.contains("A brass lantern."));
+    assert!(transcript.contains("The lantern flickers on."));
+    // Things left behind can't be
+    assert!(!transcript.contains("You see nothing special about the barrel."));
+}
@@ -0,0 +1,71 @@
+use bevy::prelude::*;
+use bevy_text_adventure::harness::TranscriptEntry;
+use bevy_text_adventure::prelude::*;
+
+mod common;
+
+use common::spawn_start_room;
+
+fn open_trunk(iomgr: Res<IOManager>) {
+    iomgr.println("The trunk is empty.");
+}
+
+fn build(mut commands: Commands) {
+    let trunk = commands
+        .spawn(Name("trunk"))
+        .on_interact(WordType::Open, open_trunk)
+        .id();
+    let rug = commands.spawn(Name("rug")).id();
+
+    spawn_start_room(
+        &mut commands,
+        Room {
+            name: "Attic",
+            description: Some("A dusty attic."),
+        },
+        &[trunk, rug],
+    );
+}
+
+// The output printed in response to `input`
+fn response_to(transcript: &Transcript, input: &str) -> String {
+    let index = transcript
+        .entries
+        .iter()
+        .position(|entry| *entry == TranscriptEntry::Input(input.to_owned()))
+        .unwrap();
+    match transcript.entries.get(index + 1) {
+        Some(TranscriptEntry::Output(output)) => output.clone(),
+        _ => String::new(),
+    }
+}
+
+#[test]
+fn look_around_prints_the_description_once() {
+    let transcript = AdventureHarness::new(["look"])
+        .add_startup_system(build)
+        .run();
+
+    // The description, then what's in the room, then the next prompt - without the
+    // autoprompt printing the description again
+    let response = response_to(&transcript, "look");
+    assert!(
+        response.starts_with("A dusty attic.\nYou see: trunk and rug.\n(What do you do?)"),
+        "Unexpected response: {:?}",
+        response
+    );
+    assert_eq!(response.matches("A dusty attic.").count(), 1);
+}
+
+#[test]
+fn look_at_things_without_descriptions() {
+    let transcript = AdventureHarness::new(["look at trunk", "look at rug"])
+        .add_startup_system(build)
+        .run();
+
+    // The trunk has handlers, just not for looking
+    assert!(response_to(&transcript, "look at trunk")
+        .starts_with("You see nothing special about the trunk."));
+    assert!(response_to(&transcript, "look at rug")
+        .starts_with("You see nothing special about the rug."));
+}
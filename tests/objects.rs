@@ -0,0 +1,91 @@
+use bevy::prelude::*;
+use bevy_text_adventure::components::Name;
+use bevy_text_adventure::prelude::*;
+
+mod common;
+
+use common::spawn_start_room;
+
+fn give_to_joseph(context: In<InteractionContext>, iomgr: Res<IOManager>, names: Query<&Name>) {
+    let gift = context.0.secondary.and_then(|gift| names.get(gift).ok());
+    iomgr.println(format!(
+        "Joseph takes the {}.",
+        gift.map_or("nothing", |name| name.0)
+    ));
+}
+
+fn wave_bible(iomgr: Res<IOManager>) {
+    iomgr.println("You wave the bible around.");
+}
+
+fn open_door(context: In<InteractionContext>, iomgr: Res<IOManager>, names: Query<&Name>) {
+    let tool = context.0.secondary.and_then(|tool| names.get(tool).ok());
+    iomgr.println(format!(
+        "You open the door with the {}.",
+        tool.map_or("nothing", |name| name.0)
+    ));
+}
+
+fn open_key(iomgr: Res<IOManager>) {
+    iomgr.println("The key doesn't open.");
+}
+
+fn build(mut commands: Commands) {
+    let bible = commands
+        .spawn(Name("bible"))
+        .on_interact(WordType::Give, wave_bible)
+        .id();
+    let joseph = commands
+        .spawn(Name("joseph"))
+        .on_interact(WordType::Give, give_to_joseph)
+        .id();
+    let pew = commands.spawn(Name("pew")).id();
+    let door = commands
+        .spawn(Name("door"))
+        .on_interact(WordType::Open, open_door)
+        .id();
+    let key = commands
+        .spawn(Name("key"))
+        .on_interact(WordType::Open, open_key)
+        .id();
+
+    spawn_start_room(
+        &mut commands,
+        Room {
+            name: "Church",
+            description: None,
+        },
+        &[bible, joseph, pew, door, key],
+    );
+}
+
+#[test]
+fn give_bible_to_joseph() {
+    let transcript = AdventureHarness::new(["give bible to joseph"])
+        .add_startup_system(build)
+        .run();
+
+    // Joseph's handler runs, and sees the bible
+    assert!(transcript.contains("Joseph takes the bible."));
+    assert!(!transcript.contains("You wave the bible around."));
+}
+
+#[test]
+fn direct_object_handles_what_the_indirect_object_cant() {
+    let transcript = AdventureHarness::new(["give bible to pew"])
+        .add_startup_system(build)
+        .run();
+
+    assert!(transcript.contains("You wave the bible around."));
+}
+
+#[test]
+fn with_goes_to_the_direct_object() {
+    let transcript = AdventureHarness::new(["open door with key"])
+        .add_startup_system(build)
+        .run();
+
+    // The key has an Open handler too, but it's only what the door is opened with
+    assert!(transcript.contains("You open the door with the key."));
+    assert!(!transcript.contains("The key doesn't open."));
+}
@@ -0,0 +1,18 @@
+use bevy_text_adventure::prelude::*;
+use std::time::Duration;
+
+fn iomgr(inputs: &[&str]) -> (IOManager, ScriptedIo) {
+    let io = ScriptedIo::new(inputs.iter().copied());
+    let mut iomgr = IOManager::with_io(io.clone());
+    iomgr.set_delays(Duration::ZERO, Duration::ZERO, Duration::ZERO);
+    (iomgr, io)
+}
+
+#[test]
+fn options_prompt_rejects_out_of_range_choices() {
+    let (iomgr, io) = iomgr(&["0", "3", "two", "2"]);
+
+    assert_eq!(iomgr.options_prompt(vec!["Left", "Right"]), 2);
+    // Every input was needed to get a valid choice
+    assert!(io.transcript().unused_inputs.is_empty());
+}
@@ -0,0 +1,68 @@
+use bevy::prelude::*;
+use bevy_text_adventure::prelude::*;
+
+mod common;
+
+use common::spawn_start_room;
+
+fn talk_to_joseph(iomgr: Res<IOManager>) {
+    iomgr.println("Joseph nods at you.");
+}
+
+fn build(mut commands: Commands) {
+    let coin = commands
+        .spawn((
+            Name("coin"),
+            Portable,
+            Description("A gold coin.".to_owned()),
+        ))
+        .id();
+    let lamp = commands.spawn((Name("lamp"), Portable)).id();
+    let joseph = commands
+        .spawn((Name("joseph"), Pronouns(vec!["him"])))
+        .on_interact(WordType::Talk, talk_to_joseph)
+        .id();
+
+    spawn_start_room(
+        &mut commands,
+        Room {
+            name: "Chapel",
+            description: None,
+        },
+        &[coin, lamp, joseph],
+    );
+}
+
+#[test]
+fn it_is_the_last_thing_mentioned() {
+    let transcript = AdventureHarness::new(["look at coin", "look", "take it"])
+        .add_startup_system(build)
+        .run();
+
+    assert!(transcript.contains("A gold coin."));
+    // Looking around doesn't mention anything, so "it" is still the coin
+    assert!(transcript.contains("You take the coin."));
+}
+
+#[test]
+fn entities_can_have_their_own_pronouns() {
+    let transcript = AdventureHarness::new(["look at joseph", "talk to him", "take it"])
+        .add_startup_system(build)
+        .run();
+
+    assert!(transcript.contains("Joseph nods at you."));
+    // Joseph isn't an "it"
+    assert!(!transcript.contains("You take"));
+}
+
+#[test]
+fn them_is_everything_mentioned() {
+    let transcript = AdventureHarness::new(["put coin on lamp", "take them", "i"])
+        .add_startup_system(build)
+        .run();
+
+    assert!(!transcript.contains("Which do you mean"));
+    assert!(transcript.contains("You take the coin."));
+    assert!(transcript.contains("You take the lamp."));
+    assert!(transcript.contains("You're carrying: coin and lamp."));
+}
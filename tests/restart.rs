@@ -0,0 +1,63 @@
+use bevy::prelude::*;
+use bevy_text_adventure::prelude::*;
+
+mod common;
+
+use common::spawn_start_room;
+
+fn take_key(iomgr: Res<IOManager>, mut flags: ResMut<Flags>) {
+    iomgr.println("You take the key.");
+    flags.set("key", true);
+}
+
+fn check_key(iomgr: Res<IOManager>, flags: Res<Flags>) {
+    match flags.is_set("key") {
+        true => iomgr.println("You already have the key."),
+        false => iomgr.println("The key is on the floor."),
+    }
+}
+
+fn build(mut commands: Commands) {
+    let key = commands
+        .spawn(Name("key"))
+        .on_interact(WordType::Take, take_key)
+        .on_interact(WordType::Look, check_key)
+        .id();
+
+    spawn_start_room(
+        &mut commands,
+        Room {
+            name: "Cell",
+            description: None,
+        },
+        &[key],
+    );
+}
+
+#[test]
+fn restart_rebuilds_the_world() {
+    let transcript = AdventureHarness::new(["take key", "restart", "y", "look at key"])
+        .add_world_builder(build)
+        .run();
+
+    assert_eq!(
+        transcript.output().matches("You're in the cell.").count(),
+        2
+    );
+    assert!(transcript.contains("The key is on the floor."));
+}
+
+#[test]
+fn restart_without_world_builders_is_refused() {
+    let transcript = AdventureHarness::new(["take key", "restart", "look at key"])
+        .add_startup_system(build)
+        .run();
+
+    assert!(transcript.contains("Sorry, this game can't be restarted."));
+    // The game carries on as it was
+    assert_eq!(
+        transcript.output().matches("You're in the cell.").count(),
+        1
+    );
+    assert!(transcript.contains("You already have the key."));
+}
@@ -0,0 +1,248 @@
+use bevy::prelude::*;
+use bevy_text_adventure::prelude::*;
+use bevy_text_adventure::save::{self, SAVE_VERSION};
+use std::env;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+mod common;
+
+use common::spawn_start_room;
+
+fn ring_bell(iomgr: Res<IOManager>, mut flags: ResMut<Flags>) {
+    iomgr.println("Ding!");
+    flags.set("rang", true);
+}
+
+fn look_at_bell(iomgr: Res<IOManager>, flags: Res<Flags>) {
+    match flags.is_set("rang") {
+        true => iomgr.println("The bell is still ringing."),
+        false => iomgr.println("The bell is still."),
+    }
+}
+
+fn chase_rat(
+    context: In<InteractionContext>,
+    iomgr: Res<IOManager>,
+    mut health: Query<&mut Health>,
+) {
+    iomgr.println("The rat is gone for good.");
+    health.get_mut(context.0.target).unwrap().set_health(0);
+}
+
+fn build(mut commands: Commands) {
+    let coin = commands.spawn((Name("coin"), Portable)).id();
+    let bell = commands
+        .spawn(Name("bell"))
+        .on_interact(WordType::Use, ring_bell)
+        .on_interact(WordType::Look, look_at_bell)
+        .id();
+    let rat = commands
+        .spawn((Name("rat"), Health(1)))
+        .on_interact(WordType::Break, chase_rat)
+        .id();
+
+    spawn_start_room(
+        &mut commands,
+        Room {
+            name: "Museum",
+            description: None,
+        },
+        &[coin, bell, rat],
+    );
+}
+
+// The same museum, after the coin was stolen
+fn build_without_coin(mut commands: Commands) {
+    let bell = commands
+        .spawn(Name("bell"))
+        .on_interact(WordType::Look, look_at_bell)
+        .id();
+
+    spawn_start_room(
+        &mut commands,
+        Room {
+            name: "Museum",
+            description: None,
+        },
+        &[bell],
+    );
+}
+
+// An empty save directory just for one test
+fn save_directory(test: &str) -> PathBuf {
+    let directory = env::temp_dir().join("adventure_save_tests").join(format!(
+        "{}-{}",
+        test,
+        std::process::id()
+    ));
+    // Clear out saves from earlier runs
+    let _ = fs::remove_dir_all(&directory);
+    directory
+}
+
+fn harness(directory: &Path, inputs: &[&str]) -> AdventureHarness {
+    let mut harness = AdventureHarness::new(inputs.iter().copied());
+    harness
+        .app()
+        .insert_resource(SaveSettings::in_directory(directory));
+    harness
+}
+
+#[test]
+fn portable_items_are_saved_and_loaded() {
+    let directory = save_directory("portable_items");
+
+    let transcript = harness(&directory, &["take coin", "save", "1", "with coin"])
+        .add_world_builder(build)
+        .run();
+    assert!(transcript.contains("Game saved."));
+
+    // Entity IDs change between runs, so push the game's entities back a little
+    let mut harness = harness(&directory, &["load", "1", "i", "look", "drop coin"]);
+    harness.app().world.spawn_empty();
+    harness.app().world.spawn_empty();
+    let transcript = harness.add_world_builder(build).run();
+
+    assert!(transcript.contains("Game loaded."));
+    assert!(transcript.contains("You're carrying: coin."));
+    assert!(transcript.contains("You see: bell and rat."));
+    assert!(transcript.contains("You drop the coin."));
+}
+
+#[test]
+fn saves_from_other_versions_are_refused() {
+    let directory = save_directory("other_versions");
+
+    harness(&directory, &["use bell", "save", "1", "rang"])
+        .add_world_builder(build)
+        .run();
+
+    // Pretend the save was made by a newer version of the game
+    let path = SaveSettings::in_directory(&directory).slot_path("save-1");
+    let save = fs::read_to_string(&path).unwrap();
+    let save = save.replace(
+        &format!("version: {},", SAVE_VERSION),
+        &format!("version: {},", SAVE_VERSION + 1),
+    );
+    fs::write(&path, save).unwrap();
+
+    let transcript = harness(&directory, &["load", "1", "look at bell"])
+        .add_world_builder(build)
+        .run();
+
+    assert!(transcript.contains(&format!(
+        "Couldn't load the game: the save file is version {}, but this game uses version {}",
+        SAVE_VERSION + 1,
+        SAVE_VERSION
+    )));
+    assert!(transcript.contains("The bell is still."));
+}
+
+#[test]
+fn loading_a_missing_entity_changes_nothing() {
+    let directory = save_directory("missing_entity");
+
+    harness(
+        &directory,
+        &["take coin", "use bell", "save", "1", "with coin"],
+    )
+    .add_world_builder(build)
+    .run();
+
+    let transcript = harness(&directory, &["load", "1", "look at bell"])
+        .add_world_builder(build_without_coin)
+        .run();
+
+    assert!(transcript.contains("Couldn't load the game: `coin` doesn't exist"));
+    // The bell was rung in the save, but the flag wasn't loaded
+    assert!(transcript.contains("The bell is still."));
+}
+
+#[test]
+fn loading_brings_the_dead_back() {
+    let directory = save_directory("death");
+
+    let transcript = harness(
+        &directory,
+        &[
+            "save",
+            "1",
+            "with rat",
+            "use bell",
+            "break rat",
+            "load",
+            "1",
+            "look at bell",
+            "break rat",
+        ],
+    )
+    .add_world_builder(build)
+    .run();
+
+    assert!(transcript.contains("Game loaded."));
+    assert!(transcript.contains("The bell is still."));
+    // The rat was alive in the save, so it can be chased off again
+    assert_eq!(
+        transcript
+            .output()
+            .matches("The rat is gone for good.")
+            .count(),
+        2
+    );
+}
+
+#[test]
+fn overwriting_a_slot_asks_first() {
+    let directory = save_directory("overwrite");
+
+    let transcript = harness(
+        &directory,
+        &[
+            "save", "1", "first", "use bell", // Save before ringing the bell
+            "save", "2", "n", // Change our mind about overwriting it
+            "save", "2", "y", // Then overwrite it after all
+        ],
+    )
+    .add_world_builder(build)
+    .run();
+
+    assert_eq!(transcript.output().matches("Overwrite 'first'?").count(), 2);
+    assert_eq!(transcript.output().matches("Game saved.").count(), 2);
+
+    // The slot kept its name, and has the bell ringing in it
+    let slots = save::list_slots(&directory);
+    assert_eq!(slots.len(), 1);
+    assert_eq!(slots[0].save.label, "first");
+    assert!(slots[0].save.flags.is_set("rang"));
+}
+
+#[test]
+fn cancelling_saves_and_loads() {
+    let directory = save_directory("cancel");
+
+    // Load with no saves, cancel saving, save, ring the bell, then cancel loading
+    let transcript = harness(
+        &directory,
+        &[
+            "load",
+            "save",
+            "2",
+            "save",
+            "1",
+            "first",
+            "use bell",
+            "load",
+            "2",
+            "look at bell",
+        ],
+    )
+    .add_world_builder(build)
+    .run();
+
+    assert!(transcript.contains("There are no saves to load."));
+    assert_eq!(transcript.output().matches("Game saved.").count(), 1);
+    assert!(!transcript.contains("Game loaded."));
+    assert!(transcript.contains("The bell is still ringing."));
+    assert_eq!(save::list_slots(&directory).len(), 1);
+}
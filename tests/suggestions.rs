@@ -0,0 +1,66 @@
+use bevy::prelude::*;
+use bevy_text_adventure::components::Name;
+use bevy_text_adventure::prelude::*;
+
+mod common;
+
+use common::spawn_start_room;
+
+fn talk_to_guard(iomgr: Res<IOManager>) {
+    iomgr.println("The guard ignores you.");
+}
+
+// The garden understands "walk" itself, even though it isn't a verb
+fn walk(iomgr: Res<IOManager>, command: Res<PlayerCommand>) {
+    if command.unknown_words.iter().any(|word| word == "walk") {
+        iomgr.println("You walk around the garden.");
+    }
+}
+
+fn build(mut commands: Commands) {
+    let guard = commands
+        .spawn(Name("guard"))
+        .on_interact(WordType::Talk, talk_to_guard)
+        .id();
+
+    spawn_start_room(
+        &mut commands,
+        Room {
+            name: "Garden",
+            description: None,
+        },
+        &[guard],
+    )
+    .on_interact(WordType::Any, walk);
+}
+
+#[test]
+fn typos_can_be_fixed() {
+    let transcript = AdventureHarness::new(["tlak to guard", "y"])
+        .add_startup_system(build)
+        .run();
+
+    assert!(transcript.contains("I don't know how to 'tlak'. Did you mean 'talk'?"));
+    assert!(transcript.contains("The guard ignores you."));
+}
+
+#[test]
+fn handled_words_arent_typos() {
+    let transcript = AdventureHarness::new(["walk"])
+        .add_startup_system(build)
+        .run();
+
+    assert!(transcript.contains("You walk around the garden."));
+    assert!(!transcript.contains("Did you mean"));
+}
+
+#[test]
+fn turning_down_a_fix_gives_the_usual_reply() {
+    let transcript = AdventureHarness::new(["tlak guard", "n"])
+        .add_startup_system(build)
+        .run();
+
+    assert!(transcript.contains("Did you mean 'talk'?"));
+    assert!(!transcript.contains("The guard ignores you."));
+    assert!(transcript.contains("I don't understand that action."));
+}
@@ -0,0 +1,27 @@
+use bevy_text_adventure::prelude::*;
+use std::{env, fs};
+
+mod common;
+
+use common::build;
+
+#[test]
+fn invasion_transcript() {
+    AdventureHarness::new(Vec::<String>::new())
+        .add_startup_system(build)
+        .run_transcript("tests/invasion.transcript");
+}
+
+#[test]
+#[should_panic(expected = "doesn't match the game's output")]
+fn changed_output_fails() {
+    let path = env::temp_dir().join("bevy_text_adventure_changed_output.transcript");
+    let expected = fs::read_to_string("tests/invasion.transcript")
+        .unwrap()
+        .replace("You grab your kids.", "You leave your kids behind.");
+    fs::write(&path, expected).unwrap();
+
+    AdventureHarness::new(Vec::<String>::new())
+        .add_startup_system(build)
+        .run_transcript(&path);
+}
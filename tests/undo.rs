@@ -0,0 +1,143 @@
+use bevy::prelude::*;
+use bevy_text_adventure::prelude::*;
+
+mod common;
+
+use common::spawn_start_room;
+
+// Something a handler spawns outside of any room
+#[derive(Component)]
+struct Gust;
+
+fn take_coin(iomgr: Res<IOManager>, mut flags: ResMut<Flags>) {
+    iomgr.println("You pocket the coin.");
+    flags.set("coin", true);
+}
+
+fn break_vase(context: In<InteractionContext>, iomgr: Res<IOManager>, mut commands: Commands) {
+    iomgr.println("The vase shatters.");
+    commands.entity(context.0.target).despawn_recursive();
+}
+
+fn count_coins(iomgr: Res<IOManager>, flags: Res<Flags>) {
+    match flags.is_set("coin") {
+        true => iomgr.println("You have a coin."),
+        false => iomgr.println("You don't have a coin."),
+    }
+}
+
+fn shake_tree(
+    mut commands: Commands,
+    iomgr: Res<IOManager>,
+    room: Query<Entity, With<ActiveRoom>>,
+) {
+    iomgr.println("An apple falls from the tree.");
+    let apple = commands.spawn(Name("apple")).id();
+    commands.entity(room.single()).add_child(apple);
+    commands.spawn(Gust);
+}
+
+fn look_at_tree(iomgr: Res<IOManager>, gusts: Query<(), With<Gust>>) {
+    if !gusts.is_empty() {
+        iomgr.println("The tree sways in the wind.");
+    }
+}
+
+fn scare_bird(
+    context: In<InteractionContext>,
+    iomgr: Res<IOManager>,
+    mut health: Query<&mut Health>,
+) {
+    iomgr.println("The bird flies off for good.");
+    health.get_mut(context.0.target).unwrap().set_health(0);
+}
+
+fn build(mut commands: Commands) {
+    let coin = commands
+        .spawn(Name("coin"))
+        .on_interact(WordType::Take, take_coin)
+        .on_interact(WordType::Look, count_coins)
+        .id();
+    let vase = commands
+        .spawn(Name("vase"))
+        .on_interact(WordType::Break, break_vase)
+        .id();
+    let tree = commands
+        .spawn(Name("tree"))
+        .on_interact(WordType::Move, shake_tree)
+        .on_interact(WordType::Look, look_at_tree)
+        .id();
+    let bird = commands
+        .spawn((Name("bird"), Health(1)))
+        .on_interact(WordType::Break, scare_bird)
+        .id();
+
+    spawn_start_room(
+        &mut commands,
+        Room {
+            name: "Museum",
+            description: None,
+        },
+        &[coin, vase, tree, bird],
+    );
+}
+
+#[test]
+fn undo_restores_flags() {
+    let transcript = AdventureHarness::new(["take coin", "undo", "undo", "look at coin"])
+        .add_startup_system(build)
+        .run();
+
+    assert!(transcript.contains("You pocket the coin."));
+    assert!(transcript.contains("Undone."));
+    assert!(transcript.contains("You don't have a coin."));
+    assert!(transcript.contains("There's nothing to undo."));
+}
+
+#[test]
+fn undo_refuses_to_bring_back_despawned_entities() {
+    let transcript = AdventureHarness::new(["take coin", "break vase", "undo", "undo"])
+        .add_startup_system(build)
+        .run();
+
+    assert!(transcript.contains("The vase shatters."));
+    assert!(transcript.contains("That can't be undone."));
+    assert!(!transcript.contains("Undone."));
+    // Every older snapshot had the vase in it too, so they're gone
+    assert!(transcript.contains("There's nothing to undo."));
+}
+
+#[test]
+fn undo_despawns_new_entities() {
+    let transcript = AdventureHarness::new(["move tree", "undo", "look", "look at apple"])
+        .add_startup_system(build)
+        .run();
+
+    assert!(transcript.contains("An apple falls from the tree."));
+    assert!(transcript.contains("Undone."));
+    assert!(transcript.contains("You see: coin, vase, tree and bird."));
+    assert!(!transcript.contains("You see nothing special about the apple."));
+}
+
+#[test]
+fn undo_brings_back_the_dead() {
+    let transcript = AdventureHarness::new(["break bird", "look", "undo", "undo", "look"])
+        .add_startup_system(build)
+        .run();
+
+    assert!(transcript.contains("The bird flies off for good."));
+    assert!(transcript.contains("You see: coin, vase and tree."));
+    assert_eq!(transcript.output().matches("Undone.").count(), 2);
+    assert!(transcript.contains("You see: coin, vase, tree and bird."));
+}
+
+#[test]
+fn undo_leaves_other_entities_alone() {
+    let transcript = AdventureHarness::new(["move tree", "undo", "look at tree"])
+        .add_startup_system(build)
+        .run();
+
+    assert!(transcript.contains("Undone."));
+    // The gust isn't in a room, so undo doesn't know about it
+    assert!(transcript.contains("The tree sways in the wind."));
+}
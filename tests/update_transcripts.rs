@@ -0,0 +1,43 @@
+// Setting UPDATE_TRANSCRIPTS_VAR affects every test in the same binary, so this gets its own file
+
+use bevy_text_adventure::harness::UPDATE_TRANSCRIPTS_VAR;
+use bevy_text_adventure::prelude::*;
+use std::{env, fs};
+
+mod common;
+
+use common::build;
+
+fn run(path: &std::path::Path) {
+    AdventureHarness::new(Vec::<String>::new())
+        .add_startup_system(build)
+        .run_transcript(path);
+}
+
+#[test]
+fn update_mode_writes_transcripts() {
+    let path = env::temp_dir().join("bevy_text_adventure_update.transcript");
+    let _ = fs::remove_file(&path);
+
+    env::set_var(UPDATE_TRANSCRIPTS_VAR, "1");
+
+    // Missing files are created, with just the game's output up to the first prompt
+    run(&path);
+    let created = TranscriptFile::load(&path).unwrap();
+    assert!(created.inputs.is_empty());
+    assert!(created.outputs[0].contains("You wake up to the sound of gunshots."));
+
+    // Changed files are rewritten with the game's actual output, keeping their inputs
+    fs::write(&path, "Something else entirely\n> take kids\n").unwrap();
+    run(&path);
+    let updated = TranscriptFile::load(&path).unwrap();
+    assert_eq!(updated.inputs, vec!["take kids"]);
+    assert!(updated.outputs[0].contains("You wake up to the sound of gunshots."));
+    assert!(updated.outputs[1].contains("You grab your kids."));
+
+    // Once updated, the file matches the game
+    env::remove_var(UPDATE_TRANSCRIPTS_VAR);
+    run(&path);
+
+    fs::remove_file(&path).unwrap();
+}
@@ -0,0 +1,27 @@
+use bevy::prelude::*;
+use bevy_text_adventure::prelude::*;
+
+fn build(mut commands: Commands) {
+    commands.spawn((
+        Room {
+            name: "Yard",
+            description: None,
+        },
+        Exits::new().with(Direction::North, RoomId::new("Feild")),
+        ActiveRoom,
+    ));
+    commands.spawn(Room {
+        name: "Field",
+        description: None,
+    });
+}
+
+// Worlds are only validated in debug builds by default
+#[cfg(debug_assertions)]
+#[test]
+#[should_panic(expected = "The `north` exit in room `Yard` leads to `Feild`, which does not exist")]
+fn errors_stop_the_game() {
+    AdventureHarness::new(["look"])
+        .add_world_builder(build)
+        .run();
+}
@@ -0,0 +1,57 @@
+use bevy::prelude::*;
+use bevy_text_adventure::components::Name;
+use bevy_text_adventure::prelude::*;
+
+mod common;
+
+use common::spawn_start_room;
+
+const PRAY: VerbId = VerbId("pray");
+
+fn pray_at_altar(context: In<InteractionContext>, iomgr: Res<IOManager>) {
+    assert_eq!(context.0.word_type, Some(WordType::Custom(PRAY)));
+    iomgr.println("You kneel and pray.");
+}
+
+fn build(mut commands: Commands) {
+    let altar = commands
+        .spawn(Name("altar"))
+        .on_interact(WordType::Custom(PRAY), pray_at_altar)
+        .id();
+
+    spawn_start_room(
+        &mut commands,
+        Room {
+            name: "Chapel",
+            description: None,
+        },
+        &[altar],
+    );
+}
+
+fn harness(inputs: &[&str]) -> AdventureHarness {
+    let mut harness = AdventureHarness::new(inputs.iter().copied()).add_startup_system(build);
+    harness.app().add_verb(PRAY, &["pray", "worship"]);
+    harness
+}
+
+#[test]
+fn custom_verbs_run_their_handlers() {
+    let transcript = harness(&["worship at the altar"]).run();
+
+    assert!(transcript.contains("You kneel and pray."));
+}
+
+#[test]
+fn help_lists_custom_verbs_last() {
+    let transcript = harness(&["help"]).run();
+
+    let output = transcript.output();
+    let look = output
+        .find("- look (examine, look, observe, study)")
+        .unwrap();
+    let give = output.find("- give (give, hand)").unwrap();
+    let pray = output.find("- pray (pray, worship)").unwrap();
+    assert!(look < give && give < pray);
+    assert!(output.contains("Or: inventory, save, load, undo, restart, help"));
+}